@@ -79,6 +79,8 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     User,
+    #[sea_orm(has_many = "super::item::Entity")]
+    Item,
 }
 
 impl Related<super::user::Entity> for Entity {
@@ -87,4 +89,10 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
+impl Related<super::item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Item.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
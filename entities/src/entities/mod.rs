@@ -2,5 +2,9 @@
 
 pub mod prelude;
 
+pub mod account_access_log;
 pub mod character;
+pub mod ip_ban;
+pub mod item;
+pub mod sea_orm_active_enums;
 pub mod user;
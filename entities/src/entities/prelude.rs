@@ -1,4 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+pub use super::account_access_log::Entity as AccountAccessLog;
 pub use super::character::Entity as Character;
+pub use super::ip_ban::Entity as IpBan;
+pub use super::item::Entity as Item;
 pub use super::user::Entity as User;
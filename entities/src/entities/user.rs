@@ -12,6 +12,9 @@ pub struct Model {
     pub ban_ip: Option<String>,
     pub password: String,
     pub ban_duration: Option<i64>,
+    pub pi_agreement: bool,
+    pub last_server: i16,
+    pub pin: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,225 @@
+use crate::entities::account_access_log::{ActiveModel, Column, Entity, Model};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, QueryOrder, QuerySelect};
+
+impl Model {
+    ///
+    /// Opens a new session record when the account enters a game server.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn record_login(
+        db_pool: &DatabaseConnection,
+        account: &str,
+        ip: Option<String>,
+        game_server: Option<u8>,
+        now: i64,
+    ) -> Result<Model, DbErr> {
+        ActiveModel {
+            id: ActiveValue::NotSet,
+            account: ActiveValue::Set(account.to_string()),
+            ip: ActiveValue::Set(ip),
+            pc_ip: ActiveValue::Set(None),
+            hop1: ActiveValue::Set(None),
+            hop2: ActiveValue::Set(None),
+            hop3: ActiveValue::Set(None),
+            hop4: ActiveValue::Set(None),
+            game_server: ActiveValue::Set(game_server.map(i16::from)),
+            login_at: ActiveValue::Set(now),
+            logout_at: ActiveValue::Set(None),
+        }
+        .insert(db_pool)
+        .await
+    }
+    ///
+    /// Stores the trace reported by the game server in the open session of the account,
+    /// a new record is created if there is no open session.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn record_tracert(
+        db_pool: &DatabaseConnection,
+        account: &str,
+        game_server: Option<u8>,
+        pc_ip: &str,
+        hops: [&str; 4],
+        now: i64,
+    ) -> Result<Model, DbErr> {
+        let record = match Self::find_open_session(db_pool, account).await? {
+            Some(record) => record,
+            None => Self::record_login(db_pool, account, None, game_server, now).await?,
+        };
+        let mut active_model: ActiveModel = record.into();
+        active_model.pc_ip = ActiveValue::Set(Some(pc_ip.to_string()));
+        active_model.hop1 = ActiveValue::Set(Some(hops[0].to_string()));
+        active_model.hop2 = ActiveValue::Set(Some(hops[1].to_string()));
+        active_model.hop3 = ActiveValue::Set(Some(hops[2].to_string()));
+        active_model.hop4 = ActiveValue::Set(Some(hops[3].to_string()));
+        active_model.update(db_pool).await
+    }
+    ///
+    /// Closes all open sessions of the account.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn record_logout(
+        db_pool: &DatabaseConnection,
+        account: &str,
+        now: i64,
+    ) -> Result<u64, DbErr> {
+        let res = Entity::update_many()
+            .col_expr(Column::LogoutAt, Expr::value(now))
+            .filter(Column::Account.eq(account))
+            .filter(Column::LogoutAt.is_null())
+            .exec(db_pool)
+            .await?;
+        Ok(res.rows_affected)
+    }
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn find_open_session(
+        db_pool: &DatabaseConnection,
+        account: &str,
+    ) -> Result<Option<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Account.eq(account))
+            .filter(Column::LogoutAt.is_null())
+            .order_by_desc(Column::LoginAt)
+            .order_by_desc(Column::Id)
+            .one(db_pool)
+            .await
+    }
+    ///
+    /// Connection history of the account, the latest first.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn find_by_account(
+        db_pool: &DatabaseConnection,
+        account: &str,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Account.eq(account))
+            .order_by_desc(Column::LoginAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(db_pool)
+            .await
+    }
+    ///
+    /// Sessions opened from the IP (either the connection IP or the one reported by tracert),
+    /// the latest first.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn find_by_ip(
+        db_pool: &DatabaseConnection,
+        ip: &str,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Ip.eq(ip).or(Column::PcIp.eq(ip)))
+            .order_by_desc(Column::LoginAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(db_pool)
+            .await
+    }
+    ///
+    /// Other accounts which were ever used from the same IPs as the given one.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn find_accounts_sharing_ip(
+        db_pool: &DatabaseConnection,
+        account: &str,
+    ) -> Result<Vec<String>, DbErr> {
+        let ips: Vec<Option<String>> = Entity::find()
+            .select_only()
+            .column(Column::Ip)
+            .filter(Column::Account.eq(account))
+            .filter(Column::Ip.is_not_null())
+            .distinct()
+            .into_tuple()
+            .all(db_pool)
+            .await?;
+        let ips: Vec<String> = ips.into_iter().flatten().collect();
+        if ips.is_empty() {
+            return Ok(vec![]);
+        }
+        Entity::find()
+            .select_only()
+            .column(Column::Account)
+            .filter(Column::Ip.is_in(ips))
+            .filter(Column::Account.ne(account))
+            .distinct()
+            .order_by_asc(Column::Account)
+            .into_tuple()
+            .all(db_pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::Database;
+
+    async fn setup_db() -> DatabaseConnection {
+        let db_pool = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db_pool, None).await.unwrap();
+        db_pool
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let db_pool = setup_db().await;
+        let ip = Some("10.0.0.1".to_string());
+        Model::record_login(&db_pool, "admin", ip, Some(1), 100)
+            .await
+            .unwrap();
+        let record = Model::record_tracert(
+            &db_pool,
+            "admin",
+            Some(1),
+            "192.168.0.2",
+            ["192.168.0.1", "10.0.0.254", "", ""],
+            110,
+        )
+        .await
+        .unwrap();
+        assert_eq!(record.ip.as_deref(), Some("10.0.0.1"));
+        assert_eq!(record.pc_ip.as_deref(), Some("192.168.0.2"));
+        assert_eq!(record.login_at, 100);
+        assert_eq!(Model::record_logout(&db_pool, "admin", 200).await.unwrap(), 1);
+        assert!(Model::find_open_session(&db_pool, "admin")
+            .await
+            .unwrap()
+            .is_none());
+        let history = Model::find_by_account(&db_pool, "admin", 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].logout_at, Some(200));
+    }
+
+    #[tokio::test]
+    async fn test_accounts_sharing_ip() {
+        let db_pool = setup_db().await;
+        for (account, ip) in [
+            ("main", "10.0.0.1"),
+            ("main", "10.0.0.2"),
+            ("twink", "10.0.0.2"),
+            ("stranger", "10.0.0.3"),
+        ] {
+            Model::record_login(&db_pool, account, Some(ip.to_string()), Some(1), 100)
+                .await
+                .unwrap();
+        }
+        let shared = Model::find_accounts_sharing_ip(&db_pool, "main").await.unwrap();
+        assert_eq!(shared, vec!["twink".to_string()]);
+        let from_ip = Model::find_by_ip(&db_pool, "10.0.0.2", 10).await.unwrap();
+        assert_eq!(from_ip.len(), 2);
+    }
+}
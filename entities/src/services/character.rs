@@ -1,6 +1,10 @@
-use sea_orm::{DatabaseConnection, DbErr, JoinType, QuerySelect};
-use crate::entities::character::{Entity, Model};
-use crate::entities::user;
+use sea_orm::{
+    ActiveValue, DatabaseConnection, DbErr, JoinType, PaginatorTrait, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use sea_orm::sea_query::Func;
+use crate::entities::character::{ActiveModel, Column, Entity, Model, Relation};
+use crate::entities::{item, user};
 use sea_orm::entity::prelude::*;
 
 impl Model {
@@ -16,8 +20,236 @@ impl Model {
         let characters = Entity::find()
             .join(JoinType::InnerJoin, Entity::has_many(user::Entity).into())
             .filter(user::Column::Username.eq(username))
+            .order_by_asc(Column::Id)
             .all(db_pool)
             .await?;
         Ok(characters)
     }
-}
\ No newline at end of file
+
+    /// Returns the account name owning the character.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn find_account_name(
+        db_pool: &DatabaseConnection,
+        char_name: &str,
+    ) -> Result<Option<String>, DbErr> {
+        let Some(character) = Entity::find()
+            .filter(Column::Name.eq(char_name))
+            .one(db_pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let user = user::Entity::find_by_id(character.user_id).one(db_pool).await?;
+        Ok(user.map(|u| u.username))
+    }
+
+    /// Checks the name ignoring case, new names must not clash with existing ones.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn is_name_taken(db_pool: &DatabaseConnection, name: &str) -> Result<bool, DbErr> {
+        let count = Entity::find()
+            .filter(Expr::expr(Func::lower(Expr::col(Column::Name))).eq(name.to_lowercase()))
+            .count(db_pool)
+            .await?;
+        Ok(count > 0)
+    }
+
+    /// Inserts a new character together with its starting items, `owner` of the items is set here.
+    ///
+    /// # Errors
+    /// - `DbErr`, e.g. when the name is already taken
+    ///
+    pub async fn create(
+        db_pool: &DatabaseConnection,
+        character: ActiveModel,
+        items: Vec<item::ActiveModel>,
+    ) -> Result<Model, DbErr> {
+        let txn = db_pool.begin().await?;
+        let character = character.insert(&txn).await?;
+        for mut item in items {
+            item.owner = ActiveValue::Set(character.id);
+            item.insert(&txn).await?;
+        }
+        txn.commit().await?;
+        Ok(character)
+    }
+
+    /// Marks the character to be deleted at `delete_at`, `None` restores it.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn set_delete_at(
+        self,
+        db_pool: &DatabaseConnection,
+        delete_at: Option<DateTimeWithTimeZone>,
+    ) -> Result<Model, DbErr> {
+        let mut active_model: ActiveModel = self.into();
+        active_model.delete_at = ActiveValue::Set(delete_at);
+        active_model.update(db_pool).await
+    }
+
+    /// Remembers when the character was last selected to play.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn set_last_access(
+        self,
+        db_pool: &DatabaseConnection,
+        last_access: DateTimeWithTimeZone,
+    ) -> Result<Model, DbErr> {
+        let mut active_model: ActiveModel = self.into();
+        active_model.last_access = ActiveValue::Set(Some(last_access));
+        active_model.update(db_pool).await
+    }
+
+    /// Removes the characters together with their items.
+    /// Can run inside a transaction of the caller, a savepoint is used then.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn delete_with_items<C>(db_pool: &C, char_ids: &[i32]) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let txn = db_pool.begin().await?;
+        item::Entity::delete_many()
+            .filter(item::Column::Owner.is_in(char_ids.iter().copied()))
+            .exec(&txn)
+            .await?;
+        let deleted = Entity::delete_many()
+            .filter(Column::Id.is_in(char_ids.iter().copied()))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(deleted.rows_affected)
+    }
+
+    /// Removes characters whose deletion time has come,
+    /// returns the accounts which lost characters so their character counts can be refreshed.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn purge_deleted(
+        db_pool: &DatabaseConnection,
+        now: DateTimeWithTimeZone,
+    ) -> Result<Vec<String>, DbErr> {
+        let due: Vec<(i32, String)> = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .column(user::Column::Username)
+            .join(JoinType::InnerJoin, Relation::User.def())
+            .filter(Column::DeleteAt.lte(now))
+            .into_tuple()
+            .all(db_pool)
+            .await?;
+        if due.is_empty() {
+            return Ok(vec![]);
+        }
+        let char_ids: Vec<i32> = due.iter().map(|(id, _)| *id).collect();
+        Self::delete_with_items(db_pool, &char_ids).await?;
+        let mut accounts: Vec<String> = due.into_iter().map(|(_, account)| account).collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        Ok(accounts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entities::sea_orm_active_enums::ItemLocation;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{Database, SqlErr};
+
+    fn new_char(name: &str, user_id: i32) -> ActiveModel {
+        ActiveModel {
+            name: ActiveValue::Set(name.to_string()),
+            level: ActiveValue::Set(1),
+            user_id: ActiveValue::Set(user_id),
+            sex: ActiveValue::Set(0),
+            x: ActiveValue::Set(0),
+            y: ActiveValue::Set(0),
+            z: ActiveValue::Set(0),
+            race_id: ActiveValue::Set(0),
+            base_class_id: ActiveValue::Set(0),
+            transform_id: ActiveValue::Set(0),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_character() {
+        let db_pool = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db_pool, None).await.unwrap();
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let sword = item::ActiveModel {
+            item_id: ActiveValue::Set(2369),
+            loc: ActiveValue::Set(ItemLocation::Paperdoll),
+            loc_data: ActiveValue::Set(5),
+            ..Default::default()
+        };
+        let character = Model::create(&db_pool, new_char("Bob", user.id), vec![sword])
+            .await
+            .unwrap();
+        assert!(Model::is_name_taken(&db_pool, "bOB").await.unwrap());
+        assert!(!Model::is_name_taken(&db_pool, "Alice").await.unwrap());
+        let items = item::Model::find_paperdoll_items(&db_pool, &[character.id])
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].owner, character.id);
+        for name in ["Bob", "BOB"] {
+            let err = Model::create(&db_pool, new_char(name, user.id), vec![])
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err.sql_err(),
+                Some(SqlErr::UniqueConstraintViolation(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted() {
+        let db_pool = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db_pool, None).await.unwrap();
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let now = chrono::Utc::now().fixed_offset();
+        let bob = Model::create(&db_pool, new_char("Bob", user.id), vec![])
+            .await
+            .unwrap();
+        let alice = Model::create(&db_pool, new_char("Alice", user.id), vec![])
+            .await
+            .unwrap();
+        bob.set_delete_at(&db_pool, Some(now - chrono::TimeDelta::seconds(1)))
+            .await
+            .unwrap();
+        let alice = alice
+            .set_delete_at(&db_pool, Some(now + chrono::TimeDelta::days(1)))
+            .await
+            .unwrap();
+        assert_eq!(
+            Model::purge_deleted(&db_pool, now).await.unwrap(),
+            vec!["admin".to_string()]
+        );
+        let chars = Model::find_characters_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        assert_eq!(chars, vec![alice.clone()]);
+        let alice = alice.set_delete_at(&db_pool, None).await.unwrap();
+        assert!(alice.delete_at.is_none());
+    }
+}
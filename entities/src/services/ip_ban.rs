@@ -0,0 +1,62 @@
+use crate::entities::ip_ban::{ActiveModel, Column, Entity, Model};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveValue, Condition};
+
+impl Model {
+    ///
+    /// Returns permanent bans and timed bans which are not expired at `now` (unix timestamp).
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn find_active(db_pool: &DatabaseConnection, now: i64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(
+                Condition::any()
+                    .add(Column::ExpiresAt.is_null())
+                    .add(Column::ExpiresAt.gt(now)),
+            )
+            .all(db_pool)
+            .await
+    }
+    ///
+    /// Inserts a ban or replaces expiration and reason of the existing one for the same network.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn upsert(
+        db_pool: &DatabaseConnection,
+        network: &str,
+        expires_at: Option<i64>,
+        reason: Option<String>,
+    ) -> Result<(), DbErr> {
+        let record = ActiveModel {
+            id: ActiveValue::NotSet,
+            network: ActiveValue::Set(network.to_string()),
+            expires_at: ActiveValue::Set(expires_at),
+            reason: ActiveValue::Set(reason),
+        };
+        Entity::insert(record)
+            .on_conflict(
+                OnConflict::column(Column::Network)
+                    .update_columns([Column::ExpiresAt, Column::Reason])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+    ///
+    /// # Errors
+    /// - `DbErr`
+    pub async fn delete_by_network(
+        db_pool: &DatabaseConnection,
+        network: &str,
+    ) -> Result<u64, DbErr> {
+        let res = Entity::delete_many()
+            .filter(Column::Network.eq(network))
+            .exec(db_pool)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::entities::item::{Column, Entity, Model};
+use crate::entities::sea_orm_active_enums::ItemLocation;
+use sea_orm::entity::prelude::*;
+use sea_orm::{DatabaseConnection, DbErr, QueryOrder};
+
+impl Model {
+    /// Returns equipped items of all the given characters.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn find_paperdoll_items(
+        db_pool: &DatabaseConnection,
+        char_ids: &[i32],
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Owner.is_in(char_ids.iter().copied()))
+            .filter(Column::Loc.eq(ItemLocation::Paperdoll))
+            .order_by_asc(Column::Id)
+            .all(db_pool)
+            .await
+    }
+
+    /// Returns the items a character carries, both equipped ones and the ones in the inventory.
+    ///
+    /// # Errors
+    /// - `DbErr`
+    ///
+    pub async fn find_inventory_items(
+        db_pool: &DatabaseConnection,
+        char_id: i32,
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Owner.eq(char_id))
+            .filter(Column::Loc.is_in([ItemLocation::Inventory, ItemLocation::Paperdoll]))
+            .order_by_asc(Column::Id)
+            .all(db_pool)
+            .await
+    }
+}
@@ -1,2 +1,5 @@
+pub mod account_access_log;
 pub mod character;
+pub mod ip_ban;
+pub mod item;
 pub mod user;
\ No newline at end of file
@@ -1,21 +1,47 @@
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Func, SimpleExpr};
+use sea_orm::{ActiveValue, PaginatorTrait, QuerySelect};
+use sha1::{Digest, Sha1};
 use tokio::task::spawn_blocking;
 use tracing::error;
-use crate::entities::user::{Column, Entity, Model};
+use crate::entities::user::{ActiveModel, Column, Entity, Model};
+
+pub const USERNAME_MIN_LEN: usize = 3;
+pub const USERNAME_MAX_LEN: usize = 16;
+pub const PIN_MIN_LEN: usize = 6;
+pub const PIN_MAX_LEN: usize = 8;
+const LEGACY_DIGEST_LEN: usize = 20;
 
 impl Model {
+    /// Checks the password against argon2 PHC strings and legacy L2J hashes
+    /// (base64 encoded SHA-1), see [`Model::is_legacy_hash`].
     pub async fn verify_password(&self, password: &str) -> bool {
-        let pwd = password.to_owned();
-        let pwd_hash = self.password.clone();
+        if self.is_legacy_hash() {
+            return Self::legacy_hash(password) == self.password;
+        }
+        Self::verify_hash(&self.password, password).await
+    }
+    /// Checks the secondary password, always fails when the account has no PIN.
+    pub async fn verify_pin(&self, pin: &str) -> bool {
+        match &self.pin {
+            Some(pin_hash) => Self::verify_hash(pin_hash, pin).await,
+            None => false,
+        }
+    }
+    async fn verify_hash(hash: &str, plain: &str) -> bool {
+        let plain = plain.to_owned();
+        let hash = hash.to_owned();
         let res = spawn_blocking(move || {
-            let Ok(parsed_hash) = PasswordHash::new(&pwd_hash) else {
+            let Ok(parsed_hash) = PasswordHash::new(&hash) else {
                 error!("Can not generate a hash for password");
                 return false;
             };
             Argon2::default()
-                .verify_password(pwd.as_bytes(), &parsed_hash)
+                .verify_password(plain.as_bytes(), &parsed_hash)
                 .is_ok()
         })
             .await;
@@ -24,7 +50,72 @@ impl Model {
             false
         })
     }
+    /// PIN is `PIN_MIN_LEN..=PIN_MAX_LEN` digits, not all of them the same.
+    #[must_use]
+    pub fn is_valid_pin(pin: &str) -> bool {
+        (PIN_MIN_LEN..=PIN_MAX_LEN).contains(&pin.len())
+            && pin.chars().all(|c| c.is_ascii_digit())
+            && pin.chars().any(|c| !pin.starts_with(c))
+    }
+    ///
+    /// Stores already hashed secondary password, `None` removes it.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn set_pin_hash(
+        self,
+        db_pool: &DatabaseConnection,
+        pin_hash: Option<String>,
+    ) -> anyhow::Result<Model> {
+        let mut active_model: ActiveModel = self.into();
+        active_model.pin = ActiveValue::Set(pin_hash);
+        Ok(active_model.update(db_pool).await?)
+    }
+    /// Passwords imported from L2J are base64 encoded SHA-1 digests, they must be rehashed
+    /// with argon2 on the next successful login.
+    #[must_use]
+    pub fn is_legacy_hash(&self) -> bool {
+        !self.password.starts_with('$')
+            && STANDARD
+                .decode(&self.password)
+                .is_ok_and(|digest| digest.len() == LEGACY_DIGEST_LEN)
+    }
+    #[must_use]
+    pub fn legacy_hash(password: &str) -> String {
+        STANDARD.encode(Sha1::digest(password.as_bytes()))
+    }
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn set_password_hash(
+        self,
+        db_pool: &DatabaseConnection,
+        password_hash: String,
+    ) -> anyhow::Result<Model> {
+        let mut active_model: ActiveModel = self.into();
+        active_model.password = ActiveValue::Set(password_hash);
+        Ok(active_model.update(db_pool).await?)
+    }
+    /// Account names are stored and compared in lower case, the game server lowercases them too.
+    #[must_use]
+    pub fn normalize_username(username: &str) -> String {
+        username
+            .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+            .to_lowercase()
+    }
+    /// Checks an already normalized name: `USERNAME_MIN_LEN..=USERNAME_MAX_LEN` chars of `[a-z0-9_]`.
+    #[must_use]
+    pub fn is_valid_username(username: &str) -> bool {
+        (USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&username.len())
+            && username
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    }
+    fn username_eq(username: &str) -> SimpleExpr {
+        Expr::expr(Func::lower(Expr::col(Column::Username))).eq(Self::normalize_username(username))
+    }
     ///
+    /// Exact, case-insensitive lookup of the account.
     ///
     /// # Arguments
     ///
@@ -33,32 +124,258 @@ impl Model {
     ///
     /// returns: Result<Option<Model>, Error>
     /// # Errors
+    /// - when DB is not accessible
+    /// - when several accounts differ only by case, we can't tell which one is meant
     pub async fn find_some_by_username(
         db_pool: &DatabaseConnection,
         username: &str,
     ) -> anyhow::Result<Option<Model>> {
-        Ok(Entity::find()
-            .filter(Column::Username.contains(username))
-            .one(db_pool)
-            .await?)
+        let mut users = Entity::find()
+            .filter(Self::username_eq(username))
+            .limit(2)
+            .all(db_pool)
+            .await?;
+        if users.len() > 1 {
+            bail!("Several accounts match {username} ignoring case");
+        }
+        Ok(users.pop())
     }
     ///
+    /// Same as [`Model::find_some_by_username`], but the account must exist.
     ///
     /// # Arguments
     ///
     /// * `db_pool`:
     /// * `username`:
     ///
-    /// returns: Result<Option<Model>, Error>
+    /// returns: Result<Model, Error>
     /// # Errors
+    /// - when DB is not accessible
+    /// - when user not found
     pub async fn find_by_username(
         db_pool: &DatabaseConnection,
         username: &str,
     ) -> anyhow::Result<Model> {
-        Entity::find()
-            .filter(Column::Username.contains(username))
-            .one(db_pool)
+        Self::find_some_by_username(db_pool, username)
             .await?
             .ok_or_else(|| anyhow!("User not found {username}"))
     }
-}
\ No newline at end of file
+    ///
+    /// Creates a new account with already hashed password, the name is normalized.
+    ///
+    /// # Errors
+    /// - when the name is not valid
+    /// - when an account with the same name (ignoring case) already exists
+    /// - when DB is not accessible
+    pub async fn register(
+        db_pool: &DatabaseConnection,
+        username: &str,
+        password_hash: String,
+    ) -> anyhow::Result<Model> {
+        let username = Self::normalize_username(username);
+        if !Self::is_valid_username(&username) {
+            bail!("Invalid account name: {username}");
+        }
+        let exists = Entity::find()
+            .filter(Self::username_eq(&username))
+            .count(db_pool)
+            .await?;
+        if exists > 0 {
+            bail!("Account {username} already exists");
+        }
+        let user_record = ActiveModel {
+            id: ActiveValue::NotSet,
+            username: ActiveValue::Set(username),
+            password: ActiveValue::Set(password_hash),
+            access_level: ActiveValue::Set(0),
+            ban_duration: ActiveValue::NotSet,
+            ban_ip: ActiveValue::NotSet,
+            pi_agreement: ActiveValue::Set(false),
+            last_server: ActiveValue::Set(0),
+            pin: ActiveValue::NotSet,
+        };
+        Ok(user_record.insert(db_pool).await?)
+    }
+    ///
+    /// Remembers the game server the account entered last time, it is preselected in the server list.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn set_last_server(
+        db_pool: &DatabaseConnection,
+        username: &str,
+        server_id: u8,
+    ) -> anyhow::Result<()> {
+        Entity::update_many()
+            .col_expr(Column::LastServer, Expr::value(i16::from(server_id)))
+            .filter(Self::username_eq(username))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+    /// `ban_duration` holds the unix timestamp (in seconds) when the ban expires.
+    #[must_use]
+    pub fn is_banned_at(&self, now: i64) -> bool {
+        self.ban_duration.is_some_and(|expires_at| expires_at > now)
+    }
+    ///
+    /// Removes both account and IP ban from the user.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn lift_ban(self, db_pool: &DatabaseConnection) -> anyhow::Result<Model> {
+        let mut active_model: ActiveModel = self.into();
+        active_model.ban_duration = ActiveValue::Set(None);
+        active_model.ban_ip = ActiveValue::Set(None);
+        Ok(active_model.update(db_pool).await?)
+    }
+    ///
+    /// Returns pairs of (ip, `ban_expiration`) for all bans which are still active at `now`.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn find_banned_ips(
+        db_pool: &DatabaseConnection,
+        now: i64,
+    ) -> anyhow::Result<Vec<(String, i64)>> {
+        let bans: Vec<(Option<String>, Option<i64>)> = Entity::find()
+            .select_only()
+            .column(Column::BanIp)
+            .column(Column::BanDuration)
+            .filter(Column::BanIp.is_not_null())
+            .filter(Column::BanDuration.gt(now))
+            .into_tuple()
+            .all(db_pool)
+            .await?;
+        Ok(bans
+            .into_iter()
+            .filter_map(|(ip, expires_at)| Some((ip?, expires_at?)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::Database;
+
+    async fn setup_db() -> DatabaseConnection {
+        let db_pool = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db_pool, None).await.unwrap();
+        db_pool
+    }
+
+    async fn insert_raw(db_pool: &DatabaseConnection, username: &str) {
+        ActiveModel {
+            id: ActiveValue::NotSet,
+            username: ActiveValue::Set(username.to_string()),
+            password: ActiveValue::Set("hash".to_string()),
+            access_level: ActiveValue::Set(0),
+            ban_duration: ActiveValue::NotSet,
+            ban_ip: ActiveValue::NotSet,
+            pi_agreement: ActiveValue::Set(false),
+            last_server: ActiveValue::Set(0),
+            pin: ActiveValue::NotSet,
+        }
+        .insert(db_pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_username_validation() {
+        assert_eq!(Model::normalize_username(" Admin\0\0"), "admin");
+        assert!(Model::is_valid_username("admin_01"));
+        assert!(!Model::is_valid_username("ad"));
+        assert!(!Model::is_valid_username("admin!"));
+        assert!(!Model::is_valid_username("Admin"));
+        assert!(!Model::is_valid_username(&"a".repeat(USERNAME_MAX_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn test_exact_case_insensitive_lookup() {
+        let db_pool = setup_db().await;
+        Model::register(&db_pool, "Admin", "hash".to_string())
+            .await
+            .unwrap();
+        assert!(Model::find_some_by_username(&db_pool, "adm")
+            .await
+            .unwrap()
+            .is_none());
+        let user = Model::find_by_username(&db_pool, "ADMIN").await.unwrap();
+        assert_eq!(user.username, "admin");
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_collisions() {
+        let db_pool = setup_db().await;
+        insert_raw(&db_pool, "Player").await;
+        assert!(Model::register(&db_pool, "player", "hash".to_string())
+            .await
+            .is_err());
+        assert!(Model::register(&db_pool, "PLAYER ", "hash".to_string())
+            .await
+            .is_err());
+        assert!(Model::register(&db_pool, "player1", "hash".to_string())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_password() {
+        let db_pool = setup_db().await;
+        insert_raw(&db_pool, "veteran").await;
+        let user = Model::find_by_username(&db_pool, "veteran").await.unwrap();
+        // L2J stores base64(sha1("admin"))
+        let user = user
+            .set_password_hash(&db_pool, "0DPiKuNIrrVmD8IUCuw1hQxNqZc=".to_string())
+            .await
+            .unwrap();
+        assert!(user.is_legacy_hash());
+        assert!(user.verify_password("admin").await);
+        assert!(!user.verify_password("admin1").await);
+        assert_eq!(Model::legacy_hash("admin"), user.password);
+    }
+
+    #[tokio::test]
+    async fn test_set_last_server() {
+        let db_pool = setup_db().await;
+        insert_raw(&db_pool, "player").await;
+        Model::set_last_server(&db_pool, "Player", 2).await.unwrap();
+        let user = Model::find_by_username(&db_pool, "player").await.unwrap();
+        assert_eq!(user.last_server, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pin() {
+        use argon2::password_hash::{PasswordHasher, SaltString};
+        assert!(Model::is_valid_pin("120486"));
+        assert!(!Model::is_valid_pin("11111111"));
+        assert!(!Model::is_valid_pin("12a456"));
+        assert!(!Model::is_valid_pin("12345"));
+        let db_pool = setup_db().await;
+        insert_raw(&db_pool, "player").await;
+        let user = Model::find_by_username(&db_pool, "player").await.unwrap();
+        assert!(!user.verify_pin("120486").await);
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let pin_hash = Argon2::default()
+            .hash_password(b"120486", &salt)
+            .unwrap()
+            .to_string();
+        let user = user.set_pin_hash(&db_pool, Some(pin_hash)).await.unwrap();
+        let user = Model::find_by_username(&db_pool, &user.username).await.unwrap();
+        assert!(user.verify_pin("120486").await);
+        assert!(!user.verify_pin("120487").await);
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_legacy_accounts() {
+        let db_pool = setup_db().await;
+        insert_raw(&db_pool, "Player").await;
+        insert_raw(&db_pool, "player").await;
+        assert!(Model::find_some_by_username(&db_pool, "player")
+            .await
+            .is_err());
+    }
+}
@@ -1,7 +1,10 @@
+use super::ClientSender;
 use crate::controller::Controller;
 use crate::cp_factory::build_client_packet;
 use anyhow::{bail, Error};
+use crate::packets::to_client::CharSelectionInfo;
 use async_trait::async_trait;
+use entities::entities::{character, user};
 use entities::DBPool;
 use l2_core::config::gs::GSServer;
 use l2_core::crypt::generate_blowfish_key;
@@ -15,7 +18,7 @@ use std::sync::Arc;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tokio::sync::{Mutex, Notify};
-use tracing::{info, instrument};
+use tracing::{error, info, instrument, warn};
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(unused)]
@@ -40,6 +43,7 @@ pub struct ClientHandler {
     protocol: Option<i32>,
     status: ClientStatus,
     session_key: Option<SessionKey>,
+    pin_verified: bool,
     pub account_name: Option<String>,
 }
 impl ClientHandler {
@@ -60,12 +64,84 @@ impl ClientHandler {
     pub fn set_session_key(&mut self, session_key: SessionKey) {
         self.session_key = Some(session_key);
     }
+    /// Id the client got from the login server, it is echoed back in the character packets.
+    pub fn get_session_id(&self) -> i32 {
+        self.session_key.as_ref().map_or(0, |key| key.play_ok1)
+    }
+    pub fn get_status(&self) -> &ClientStatus {
+        &self.status
+    }
     pub fn set_status(&mut self, status: ClientStatus) {
         self.status = status;
     }
+    /// Character selection is allowed only after the secondary password (PIN) check passed.
+    pub fn is_pin_verified(&self) -> bool {
+        self.pin_verified
+    }
+    pub fn set_pin_verified(&mut self) {
+        self.pin_verified = true;
+    }
     pub fn set_encryption(&mut self, bf_key: Option<Encryption>) {
         self.blowfish = bf_key;
     }
+    pub fn get_sender(&self) -> ClientSender {
+        ClientSender {
+            tcp_writer: self.tcp_writer.clone(),
+            blowfish: self.blowfish.clone(),
+            shutdown_notifier: self.shutdown_notifier.clone(),
+        }
+    }
+    /// Characters can be touched only after the secondary password (PIN) check passed,
+    /// when the account has a PIN or the server requires one.
+    pub async fn check_pin_verified(&self, account: &str) -> anyhow::Result<()> {
+        if self.pin_verified {
+            return Ok(());
+        }
+        let required = self.controller.get_cfg().secondary_auth.required;
+        let user = user::Model::find_by_username(&self.db_pool, account).await?;
+        if required || user.pin.is_some() {
+            bail!("Secondary password of {account} is not verified");
+        }
+        Ok(())
+    }
+    /// Characters are addressed by their index in `CharSelectionInfo`.
+    pub async fn get_char_by_slot(&self, slot: i32) -> anyhow::Result<character::Model> {
+        let (ClientStatus::Authenticated, Some(account)) = (&self.status, &self.account_name)
+        else {
+            bail!("Character requested by not authenticated client");
+        };
+        self.check_pin_verified(account).await?;
+        let chars = character::Model::find_characters_by_username(&self.db_pool, account).await?;
+        usize::try_from(slot)
+            .ok()
+            .and_then(|slot| chars.into_iter().nth(slot))
+            .ok_or_else(|| anyhow::anyhow!("No character in slot {slot} for {account}"))
+    }
+    /// Sends the up-to-date character list, e.g. after a character was created or deleted.
+    pub async fn send_char_selection_info(&mut self) -> anyhow::Result<()> {
+        let Some(account) = self.account_name.clone() else {
+            bail!("Character list requested by not authenticated client");
+        };
+        let cfg = self.controller.get_cfg();
+        let mut db_pool = self.db_pool.clone();
+        let packet =
+            CharSelectionInfo::new(&account, self.get_session_id(), &cfg, &mut db_pool).await?;
+        self.send_packet(Box::new(packet)).await
+    }
+    /// Lets login server know that characters of the account were created or deleted,
+    /// the client is not punished when it fails.
+    pub async fn notify_chars_changed(&self) {
+        let Some(account) = self.account_name.as_deref() else {
+            return;
+        };
+        if let Err(e) = self
+            .controller
+            .notify_chars_changed(&self.db_pool, account)
+            .await
+        {
+            warn!("Failed to send characters of {account} to login server: {e}");
+        }
+    }
     pub fn generate_key() -> Vec<u8> {
         let mut key = generate_blowfish_key(None);
         key[8] = 0xc8;
@@ -124,6 +200,7 @@ impl PacketHandler for ClientHandler {
             status: ClientStatus::Connected,
             account_name: None,
             session_key: None,
+            pin_verified: false,
         }
     }
 
@@ -135,6 +212,18 @@ impl PacketHandler for ClientHandler {
 
     fn on_disconnect(&mut self) {
         info!("Client disconnected");
+        // if the client is not registered anymore, it was kicked and logout is already done
+        let Some(account) = self.account_name.clone() else {
+            return;
+        };
+        if self.controller.unregister_client(&account).is_some() {
+            let controller = self.controller.clone();
+            tokio::spawn(async move {
+                if let Err(e) = controller.logout_account(&account).await {
+                    error!("Failed to notify login server about logout of {account}: {e}");
+                }
+            });
+        }
     }
 
     fn get_stream_reader_mut(&self) -> &Arc<Mutex<OwnedReadHalf>> {
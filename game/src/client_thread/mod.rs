@@ -1,2 +1,4 @@
 mod handler;
-pub use handler::*;
\ No newline at end of file
+mod sender;
+pub use handler::*;
+pub use sender::*;
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use l2_core::crypt::login::Encryption;
+use l2_core::traits::handlers::PacketSender;
+use l2_core::traits::Shutdown;
+use std::sync::Arc;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::{Mutex, Notify};
+
+/// Lets other tasks (e.g. login server thread) send packets to the client or disconnect it.
+/// Shares the socket writer with the `ClientHandler` it was taken from.
+#[derive(Debug, Clone)]
+pub struct ClientSender {
+    pub(crate) tcp_writer: Arc<Mutex<OwnedWriteHalf>>,
+    pub(crate) blowfish: Option<Encryption>,
+    pub(crate) shutdown_notifier: Arc<Notify>,
+}
+
+impl Shutdown for ClientSender {
+    fn get_shutdown_listener(&self) -> Arc<Notify> {
+        self.shutdown_notifier.clone()
+    }
+}
+
+#[async_trait]
+impl PacketSender for ClientSender {
+    fn encryption(&self) -> Option<&Encryption> {
+        self.blowfish.as_ref()
+    }
+
+    async fn get_stream_writer_mut(&self) -> &Arc<Mutex<OwnedWriteHalf>> {
+        &self.tcp_writer
+    }
+}
@@ -1,16 +1,43 @@
+use crate::client_thread::ClientSender;
+use crate::ls_thread::LoginHandler;
+use crate::packets::to_client::ServerClose;
+use chrono::Utc;
 use dashmap::DashMap;
 use l2_core::config::gs::GSServer;
+use entities::entities::character;
+use entities::DBPool;
 use l2_core::dto::Player;
+use l2_core::game_objects::char_template::CharTemplates;
+use l2_core::game_objects::player;
+use l2_core::ip_ban::IpBanList;
 use l2_core::message_broker::MessageBroker;
 use l2_core::packets::common::PacketType;
+use l2_core::packets::gs_2_ls::{PlayerLogout, ReplyChars};
+use l2_core::traits::handlers::PacketSender;
+use l2_core::traits::Shutdown;
 use l2_core::traits::IpBan;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Wrong secondary passwords (PIN) entered for an account.
+#[derive(Debug, Clone, Default)]
+pub struct FailedPins {
+    count: u32,
+    locked_until: Option<i64>,
+}
 
 #[derive(Clone, Debug)]
 pub struct Controller {
     cfg: Arc<GSServer>,
     online_accounts: DashMap<String, Player>,
+    ip_ban_list: Arc<IpBanList>,
+    clients: DashMap<String, ClientSender>,
+    failed_pins: DashMap<String, FailedPins>,
+    char_templates: Arc<CharTemplates>,
+    /// Characters in the world, by the account they belong to.
+    players: DashMap<String, player::Player>,
     pub message_broker: Arc<MessageBroker<u8, PacketType>>,
 }
 
@@ -18,14 +45,72 @@ impl Controller {
     pub fn new(cfg: Arc<GSServer>) -> Self {
         let threshold = Duration::from_secs(u64::from(cfg.listeners.login_server.messages.timeout));
         Controller {
+            ip_ban_list: Arc::new(IpBanList::new(&cfg.ip_ban)),
+            char_templates: Arc::new(CharTemplates::load(&cfg.char_templates)),
             cfg,
             message_broker: MessageBroker::new(threshold),
             online_accounts: DashMap::new(),
+            clients: DashMap::new(),
+            failed_pins: DashMap::new(),
+            players: DashMap::new(),
         }
     }
+    pub async fn load_ip_bans(&self, db_pool: &DBPool) -> anyhow::Result<usize> {
+        self.ip_ban_list.load_from_db(db_pool).await
+    }
+    pub fn watch_ip_ban_file(&self) -> Option<JoinHandle<()>> {
+        self.ip_ban_list.watch_file()
+    }
+    /// Periodically removes characters whose deletion delay has passed.
+    pub fn schedule_char_purge(self: &Arc<Self>, db_pool: DBPool) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.cfg.char_deletion.purge_interval.max(1));
+        let controller = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                controller.purge_deleted_chars(&db_pool).await;
+            }
+        })
+    }
+    async fn purge_deleted_chars(&self, db_pool: &DBPool) {
+        let accounts =
+            match character::Model::purge_deleted(db_pool, Utc::now().fixed_offset()).await {
+                Ok(accounts) => accounts,
+                Err(e) => {
+                    error!("Failed to purge deleted characters: {e}");
+                    return;
+                }
+            };
+        if !accounts.is_empty() {
+            info!("Purged deleted characters of {}", accounts.join(", "));
+        }
+        for account in accounts {
+            if let Err(e) = self.notify_chars_changed(db_pool, &account).await {
+                warn!("Failed to send characters of {account} to login server: {e}");
+            }
+        }
+    }
+    /// Sends the character counts of the account to login server, it shows them in the server list.
+    pub async fn notify_chars_changed(
+        &self,
+        db_pool: &DBPool,
+        account: &str,
+    ) -> anyhow::Result<()> {
+        let chars = character::Model::find_characters_by_username(db_pool, account).await?;
+        self.message_broker
+            .notify(
+                LoginHandler::HANDLER_ID,
+                Box::new(ReplyChars::new(account.to_string(), &chars)),
+            )
+            .await
+    }
     pub fn get_cfg(&self) -> Arc<GSServer> {
         self.cfg.clone()
     }
+    pub fn get_char_templates(&self) -> Arc<CharTemplates> {
+        self.char_templates.clone()
+    }
 
     pub fn get_online_accounts(&self) -> Vec<String> {
         self.online_accounts
@@ -33,6 +118,37 @@ impl Controller {
             .map(|entry| entry.key().clone())
             .collect()
     }
+    /// Makes the client reachable by its account name, e.g. to relay messages from login server.
+    pub fn register_client(&self, account: &str, sender: ClientSender) {
+        self.clients.insert(account.to_string(), sender);
+    }
+    pub fn unregister_client(&self, account: &str) -> Option<ClientSender> {
+        self.clients.remove(account).map(|(_, sender)| sender)
+    }
+    pub fn get_client(&self, account: &str) -> Option<ClientSender> {
+        self.clients.get(account).map(|sender| sender.clone())
+    }
+    /// Closes the client connection (if any) and tells login server that the account left.
+    pub async fn kick_account(&self, account: &str) -> anyhow::Result<()> {
+        if let Some(client) = self.unregister_client(account) {
+            if let Err(e) = client.send_packet(Box::new(ServerClose::new()?)).await {
+                info!("Failed to send ServerClose to {account}: {e}");
+            }
+            client.shutdown();
+        }
+        self.logout_account(account).await
+    }
+    /// Removes the account from online ones and notifies login server.
+    pub async fn logout_account(&self, account: &str) -> anyhow::Result<()> {
+        self.remove_online_account(account);
+        self.remove_player(account);
+        self.message_broker
+            .notify(LoginHandler::HANDLER_ID, Box::new(PlayerLogout::new(account)?))
+            .await
+    }
+    pub fn remove_online_account(&self, account: &str) -> Option<Player> {
+        self.online_accounts.remove(account).map(|(_, player)| player)
+    }
     pub fn add_online_account(&self, account: String) -> Option<Player> {
         let key = account.clone();
         self.online_accounts.insert(
@@ -42,10 +158,124 @@ impl Controller {
             },
         )
     }
+    /// Puts the selected character of the account into the world, replacing the previous one if any.
+    pub fn add_player(&self, account: &str, player: player::Player) -> Option<player::Player> {
+        self.players.insert(account.to_string(), player)
+    }
+    pub fn remove_player(&self, account: &str) -> Option<player::Player> {
+        self.players.remove(account).map(|(_, player)| player)
+    }
+    pub fn with_player<F, R>(&self, account: &str, f: F) -> Option<R>
+    where
+        F: FnOnce(&player::Player) -> R,
+    {
+        self.players.get(account).map(|player| f(&player))
+    }
+    /// Checks whether the account is temporary locked because of too many wrong PINs.
+    pub fn is_pin_locked(&self, account: &str) -> bool {
+        let now = Utc::now().timestamp();
+        self.failed_pins
+            .get(account)
+            .and_then(|f| f.locked_until)
+            .is_some_and(|locked_until| locked_until > now)
+    }
+    /// Registers a wrong PIN and returns how many times in a row it was wrong,
+    /// the account gets locked once `max_attempts` is reached.
+    pub fn on_pin_failed(&self, account: &str) -> u32 {
+        let cfg = &self.cfg.secondary_auth;
+        let mut failures = self.failed_pins.entry(account.to_string()).or_default();
+        failures.count += 1;
+        let count = failures.count;
+        if cfg.max_attempts > 0 && count >= cfg.max_attempts {
+            warn!("Too many wrong PINs for {account}, locking the account");
+            *failures = FailedPins {
+                count: 0,
+                locked_until: Some(Utc::now().timestamp() + cfg.lock_duration),
+            };
+        }
+        count
+    }
+    pub fn on_pin_succeeded(&self, account: &str) {
+        self.failed_pins.remove(account);
+    }
 }
 
 impl IpBan for Controller {
-    fn is_ip_banned(&self, _: &str) -> bool {
-        false
+    fn is_ip_banned(&self, ip: &str) -> bool {
+        self.ip_ban_list.is_banned(ip)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::traits::ServerConfig;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[tokio::test]
+    async fn test_kick_account() {
+        let cfg = GSServer::from_string(include_str!("test_data/test_config.yaml"));
+        let controller = Controller::new(Arc::new(cfg));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server_side, _) = listener.accept().await.unwrap();
+        let (_, tcp_writer) = server_side.into_split();
+        let shutdown_notifier = Arc::new(tokio::sync::Notify::new());
+        controller.add_online_account("admin".to_string());
+        controller.register_client(
+            "admin",
+            ClientSender {
+                tcp_writer: Arc::new(tokio::sync::Mutex::new(tcp_writer)),
+                blowfish: None,
+                shutdown_notifier: shutdown_notifier.clone(),
+            },
+        );
+        controller.kick_account("admin").await.unwrap();
+        let mut data = [0u8; 3];
+        client.read_exact(&mut data).await.unwrap();
+        assert_eq!(data, [3, 0, 0x20]);
+        assert!(controller.get_online_accounts().is_empty());
+        assert!(controller.get_client("admin").is_none());
+        // the handler loop is waiting for this notification to close the connection
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown_notifier.notified())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pin_lock() {
+        let cfg = GSServer::from_string(include_str!("test_data/test_config.yaml"));
+        let max_attempts = cfg.secondary_auth.max_attempts;
+        let controller = Controller::new(Arc::new(cfg));
+        for attempt in 1..max_attempts {
+            assert_eq!(controller.on_pin_failed("admin"), attempt);
+            assert!(!controller.is_pin_locked("admin"));
+        }
+        controller.on_pin_succeeded("admin");
+        assert_eq!(controller.on_pin_failed("admin"), 1);
+        for _ in 1..max_attempts {
+            controller.on_pin_failed("admin");
+        }
+        assert!(controller.is_pin_locked("admin"));
+        assert!(!controller.is_pin_locked("other"));
+    }
+
+    #[tokio::test]
+    async fn test_player_leaves_on_logout() {
+        let cfg = GSServer::from_string(include_str!("test_data/test_config.yaml"));
+        let controller = Controller::new(Arc::new(cfg));
+        controller.add_online_account("admin".to_string());
+        assert!(controller
+            .add_player("admin", crate::tests::test_player())
+            .is_none());
+        let name = controller.with_player("admin", |p| p.char_model.name.clone());
+        assert_eq!(name.as_deref(), Some("Bob"));
+        assert!(controller.with_player("other", |_| ()).is_none());
+        // login server is not connected, the notification result doesn't matter here
+        let _ = controller.logout_account("admin").await;
+        assert!(controller.with_player("admin", |_| ()).is_none());
     }
 }
@@ -1,5 +1,16 @@
 use crate::client_thread::ClientHandler;
+use crate::packets::from_client::auth::AuthLogin;
+use crate::packets::from_client::char_create::CharacterCreate;
+use crate::packets::from_client::char_delete::CharacterDelete;
+use crate::packets::from_client::char_restore::CharacterRestore;
+use crate::packets::from_client::char_select::CharacterSelect;
+use crate::packets::from_client::enter_world::EnterWorld;
+use crate::packets::from_client::new_character::RequestNewCharacter;
 use crate::packets::from_client::protocol::ProtocolVersion;
+use crate::packets::from_client::say::Say2;
+use crate::packets::from_client::secondary_auth::{
+    RequestEx2ndPasswordCheck, RequestEx2ndPasswordReq, RequestEx2ndPasswordVerify,
+};
 use crate::packets::HandleablePacket;
 use l2_core::packets::common::ReadablePacket;
 use tracing::error;
@@ -7,15 +18,41 @@ use tracing::error;
 pub fn build_client_packet(
     data: &[u8],
 ) -> Option<Box<dyn HandleablePacket<HandlerType = ClientHandler>>> {
-    if data.len() <= 1 {
+    if data.is_empty() {
         return None;
     }
     match data[0] {
+        0x0C => Some(Box::new(CharacterCreate::read(data)?)),
+        0x0D => Some(Box::new(CharacterDelete::read(data)?)),
         0x0E => Some(Box::new(ProtocolVersion::read(data)?)),
-        0x2B => Some(Box::new(ProtocolVersion::read(data)?)), //auth login
+        0x11 => Some(Box::new(EnterWorld::read(data)?)),
+        0x12 => Some(Box::new(CharacterSelect::read(data)?)),
+        0x13 => Some(Box::new(RequestNewCharacter::read(data)?)),
+        0x2B => Some(Box::new(AuthLogin::read(data)?)),
+        0x49 => Some(Box::new(Say2::read(data)?)),
+        0x7B => Some(Box::new(CharacterRestore::read(data)?)),
+        0xD0 => build_ex_client_packet(data),
         _ => {
             error!("Unknown GS packet ID:0x{:02X}", data[0]);
             None
         }
     }
 }
+
+fn build_ex_client_packet(
+    data: &[u8],
+) -> Option<Box<dyn HandleablePacket<HandlerType = ClientHandler>>> {
+    if data.len() < 3 {
+        return None;
+    }
+    let ex_id = u16::from_le_bytes([data[1], data[2]]);
+    match ex_id {
+        0xA6 => Some(Box::new(RequestEx2ndPasswordCheck::read(data)?)),
+        0xA7 => Some(Box::new(RequestEx2ndPasswordVerify::read(data)?)),
+        0xA8 => Some(Box::new(RequestEx2ndPasswordReq::read(data)?)),
+        _ => {
+            error!("Unknown GS ex packet ID:0x{:02X}", ex_id);
+            None
+        }
+    }
+}
@@ -23,7 +23,6 @@ pub struct LoginHandler {
     db_pool: DBPool,
     controller: Arc<Controller>,
     shutdown_notifier: Arc<Notify>,
-    timeout: u8,
     blowfish: Encryption,
 }
 impl LoginHandler {
@@ -72,7 +71,6 @@ impl PacketHandler for LoginHandler {
             controller,
             db_pool,
             blowfish: Encryption::from_u8_key(cfg.blowfish_key.as_bytes()),
-            timeout: cfg.client.timeout,
         }
     }
 
@@ -1,8 +1,7 @@
 use tracing::error;
 use l2_core::packets::common::{GSLoginFail, ReadablePacket};
-use l2_core::packets::gs_2_ls::ChangePassword;
 use l2_core::packets::ls_2_gs::{
-    AuthGS, InitLS, KickPlayer, PlayerAuthResponse, RequestChars,
+    AuthGS, ChangePassword, InitLS, KickPlayer, PlayerAuthResponse, RequestChars,
 };
 use crate::ls_thread::LoginHandler;
 use crate::packets::HandleablePacket;
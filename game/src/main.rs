@@ -3,7 +3,7 @@ use crate::controller::Controller;
 use l2_core::config::gs::GSServer;
 use l2_core::traits::server::Server;
 use std::sync::Arc;
-use tracing::error;
+use tracing::{error, info};
 use crate::ls_thread::LoginHandler;
 
 mod client_thread;
@@ -12,6 +12,8 @@ mod cp_factory;
 mod lsp_factory;
 mod packets;
 mod ls_thread;
+#[cfg(test)]
+mod tests;
 
 pub struct GameServer;
 
@@ -37,6 +39,12 @@ pub fn main() {
         .init();
     GameServer::bootstrap("config/game.yaml", |cfg, db_pool| async move {
         let controller = Arc::new(Controller::new(cfg.clone()));
+        match controller.load_ip_bans(&db_pool).await {
+            Ok(loaded) => info!("Loaded {loaded} banned IPs"),
+            Err(e) => error!("Failed to load banned IPs: {e}"),
+        }
+        let _ban_file_watcher = controller.watch_ip_ban_file();
+        let _char_purge = controller.schedule_char_purge(db_pool.clone());
         let mut ls_handle = GameServer::connector_loop::<LoginHandler>(
             cfg.clone(),
             controller.clone(),
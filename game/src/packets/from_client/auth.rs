@@ -38,12 +38,79 @@ impl ReadablePacket for AuthLogin {
     }
 }
 
+impl AuthLogin {
+    /// Asks login server to confirm the session and lets the client in. The account must be
+    /// already marked as online, the caller removes it if this fails.
+    async fn authenticate(&self, handler: &mut ClientHandler) -> Result<(), PacketRun> {
+        let controller = handler.get_controller().clone();
+        let cfg = controller.get_cfg();
+        let session_key = SessionKey {
+            play_ok1: self.play_key_1,
+            play_ok2: self.play_key_2,
+            login_ok1: self.login_key_1,
+            login_ok2: self.login_key_2,
+        };
+        let resp = controller
+            .message_broker
+            .send_message(
+                LoginHandler::HANDLER_ID,
+                &self.login_name,
+                Box::new(PlayerAuthRequest::new(
+                    &self.login_name,
+                    session_key.clone(),
+                )?),
+            )
+            .await?;
+        match resp {
+            Some((_, PacketType::PlayerAuthResp(p))) if p.is_ok => {
+                // from now on the client is registered, so on_disconnect logs the account out
+                handler.set_status(ClientStatus::Authenticated);
+                handler.set_session_key(session_key);
+                handler.account_name = Some(self.login_name.clone());
+                controller.register_client(&self.login_name, handler.get_sender());
+                controller
+                    .message_broker
+                    .notify(
+                        LoginHandler::HANDLER_ID,
+                        Box::new(PlayerInGame::new(std::slice::from_ref(&self.login_name))?),
+                    )
+                    .await?;
+                handler
+                    .send_packet(Box::new(PlayerLoginResponse::ok()?))
+                    .await?;
+                let mut db_pool = handler.get_db_pool_mut().clone();
+                handler
+                    .send_packet(Box::new(
+                        CharSelectionInfo::new(
+                            &self.login_name,
+                            self.play_key_1,
+                            &cfg,
+                            &mut db_pool,
+                        )
+                        .await?,
+                    ))
+                    .await?;
+                Ok(())
+            }
+            _ => {
+                handler
+                    .send_packet(Box::new(PlayerLoginResponse::fail(
+                        PlayerLoginResponse::SYSTEM_ERROR_LOGIN_LATER,
+                    )?))
+                    .await?;
+                Err(PacketRun {
+                    msg: Some(format!("Login failed {}", self.login_name)),
+                })
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl HandleablePacket for AuthLogin {
     type HandlerType = ClientHandler;
     async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        let controller = handler.get_controller();
-        let _cfg = controller.get_cfg();
+        let controller = handler.get_controller().clone();
         if handler.get_protocol().is_none() || self.login_name.is_empty() {
             return Err(PacketRun {
                 msg: Some("Protocol version not set".to_string()),
@@ -52,67 +119,54 @@ impl HandleablePacket for AuthLogin {
         if handler.account_name.is_none() {
             if controller
                 .add_online_account(self.login_name.clone())
-                .is_none()
+                .is_some()
             {
-                let _session_key = SessionKey {
-                    play_ok1: self.play_key_1,
-                    play_ok2: self.play_key_2,
-                    login_ok1: self.login_key_1,
-                    login_ok2: self.login_key_2,
-                };
-                let resp = controller
-                    .message_broker
-                    .send_message(
-                        LoginHandler::HANDLER_ID,
-                        &self.login_name,
-                        Box::new(PlayerAuthRequest::new(
-                            &self.login_name,
-                            _session_key.clone(),
-                        )?),
-                    )
-                    .await?;
-                match resp {
-                    Some((_, PacketType::PlayerAuthResp(p))) if p.is_ok => {
-                        controller
-                            .message_broker
-                            .notify(
-                                LoginHandler::HANDLER_ID,
-                                Box::new(PlayerInGame::new(&[self.login_name.clone()])?),
-                            )
-                            .await?;
-                        handler.set_status(ClientStatus::Authenticated);
-                        handler.set_session_key(_session_key);
-                        handler
-                            .send_packet(Box::new(PlayerLoginResponse::ok()?))
-                            .await?;
-                        let mut db_pool = handler.get_db_pool_mut().clone();
-                        handler
-                            .send_packet(Box::new(CharSelectionInfo::new(
-                                &self.login_name,
-                                self.play_key_1,
-                                &_cfg,
-                                &mut db_pool,
-                            ).await?))
-                            .await?;
-                        // todo: set char list to handler
-                    }
-                    _ => {
-                        handler
-                            .send_packet(Box::new(PlayerLoginResponse::fail(
-                                PlayerLoginResponse::SYSTEM_ERROR_LOGIN_LATER,
-                            )?))
-                            .await?;
-                        return Err(PacketRun {
-                            msg: Some(format!("Login failed {}", self.login_name)),
-                        });
-                    }
-                }
-            } else {
                 return Err(PacketRun {
                     msg: Some(format!("Account already in game {}", self.login_name)),
                 });
             }
+            if let Err(err) = self.authenticate(handler).await {
+                controller.remove_online_account(&self.login_name);
+                return Err(err);
+            }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Controller;
+    use crate::tests::{connected_pair, test_client};
+    use l2_core::config::gs::GSServer;
+    use l2_core::tests::setup_test_db;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_online_account_removed_on_timeout() {
+        let mut cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        cfg.listeners.login_server.messages.timeout = 1;
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        // login server gets the request, but never answers
+        let (ls_stream, _fake_ls) = connected_pair().await;
+        let lh = LoginHandler::new(ls_stream, db_pool.clone(), controller.clone());
+        controller
+            .message_broker
+            .register_packet_handler(LoginHandler::HANDLER_ID, Arc::new(lh));
+        let (mut handler, _client) = test_client(db_pool, controller.clone()).await;
+        handler.set_protocol(110).unwrap();
+        let packet = AuthLogin {
+            login_name: "admin".to_string(),
+            play_key_1: 1,
+            play_key_2: 2,
+            login_key_1: 3,
+            login_key_2: 4,
+        };
+        assert!(packet.handle(&mut handler).await.is_err());
+        assert!(controller.get_online_accounts().is_empty());
+        assert!(handler.account_name.is_none());
+    }
+}
@@ -0,0 +1,329 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::packets::to_client::{CharCreateFail, CharCreateFailReason, CharCreateOk};
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use chrono::Utc;
+use entities::entities::sea_orm_active_enums::ItemLocation;
+use entities::entities::{character, item, user};
+use l2_core::game_objects::char_template::{CharTemplate, CharTemplates};
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use sea_orm::{ActiveValue, SqlErr};
+use tracing::error;
+
+#[derive(Debug, Clone)]
+pub struct CharacterCreate {
+    pub name: String,
+    pub race_id: i32,
+    pub sex: i32,
+    pub class_id: i32,
+    pub hair_style: i32,
+    pub hair_color: i32,
+    pub face: i32,
+}
+
+impl CharacterCreate {
+    const NAME_MAX_LEN: usize = 16;
+    const STARTING_VITALITY: i32 = 140_000;
+
+    /// Checks everything which doesn't need the DB and returns the template of the chosen class.
+    fn validate<'a>(
+        &self,
+        templates: &'a CharTemplates,
+    ) -> Result<&'a CharTemplate, CharCreateFailReason> {
+        if self.name.chars().count() > Self::NAME_MAX_LEN {
+            return Err(CharCreateFailReason::Name16EngChars);
+        }
+        if self.name.is_empty() || !self.name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(CharCreateFailReason::IncorrectName);
+        }
+        let template = i8::try_from(self.class_id)
+            .ok()
+            .and_then(|class_id| templates.get(class_id))
+            .filter(|template| i32::from(template.race_id) == self.race_id)
+            .ok_or(CharCreateFailReason::CreationFailed)?;
+        // female characters have more hair styles
+        let max_hair_style = if self.sex == 0 { 4 } else { 6 };
+        if !(0..=1).contains(&self.sex)
+            || !(0..=max_hair_style).contains(&self.hair_style)
+            || !(0..=3).contains(&self.hair_color)
+            || !(0..=2).contains(&self.face)
+        {
+            return Err(CharCreateFailReason::CreationFailed);
+        }
+        Ok(template)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn new_character(&self, template: &CharTemplate, user_id: i32) -> character::ActiveModel {
+        character::ActiveModel {
+            name: ActiveValue::Set(self.name.clone()),
+            level: ActiveValue::Set(1),
+            user_id: ActiveValue::Set(user_id),
+            max_hp: ActiveValue::Set(template.hp),
+            cur_hp: ActiveValue::Set(template.hp),
+            max_cp: ActiveValue::Set(template.cp),
+            cur_cp: ActiveValue::Set(template.cp),
+            max_mp: ActiveValue::Set(template.mp),
+            cur_mp: ActiveValue::Set(template.mp),
+            face: ActiveValue::Set(Some(self.face as i16)),
+            hair_style: ActiveValue::Set(Some(self.hair_style as i16)),
+            hair_color: ActiveValue::Set(Some(self.hair_color as i16)),
+            sex: ActiveValue::Set(self.sex as i16),
+            heading: ActiveValue::Set(Some(0)),
+            x: ActiveValue::Set(template.spawn.x),
+            y: ActiveValue::Set(template.spawn.y),
+            z: ActiveValue::Set(template.spawn.z),
+            race_id: ActiveValue::Set(template.race_id),
+            class_id: ActiveValue::Set(Some(template.class_id)),
+            base_class_id: ActiveValue::Set(template.class_id),
+            transform_id: ActiveValue::Set(0),
+            vitality_points: ActiveValue::Set(Self::STARTING_VITALITY),
+            created_at: ActiveValue::Set(Some(Utc::now().fixed_offset())),
+            ..Default::default()
+        }
+    }
+
+    fn starting_items(template: &CharTemplate) -> Vec<item::ActiveModel> {
+        template
+            .items
+            .iter()
+            .map(|start_item| {
+                let (loc, loc_data) = match start_item.slot {
+                    Some(slot) => (ItemLocation::Paperdoll, i32::from(slot as u8)),
+                    None => (ItemLocation::Inventory, 0),
+                };
+                item::ActiveModel {
+                    item_id: ActiveValue::Set(start_item.item_id),
+                    count: ActiveValue::Set(start_item.count),
+                    loc: ActiveValue::Set(loc),
+                    loc_data: ActiveValue::Set(loc_data),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+impl ReadablePacket for CharacterCreate {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        let name = buffer.read_string();
+        let race_id = buffer.read_i32();
+        let sex = buffer.read_i32();
+        let class_id = buffer.read_i32();
+        // int, str, con, men, dex, wit are taken from the template
+        for _ in 0..6 {
+            buffer.read_i32();
+        }
+        let hair_style = buffer.read_i32();
+        let hair_color = buffer.read_i32();
+        let face = buffer.read_i32();
+        Some(Self {
+            name,
+            race_id,
+            sex,
+            class_id,
+            hair_style,
+            hair_color,
+            face,
+        })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for CharacterCreate {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let (ClientStatus::Authenticated, Some(account)) =
+            (handler.get_status(), handler.account_name.clone())
+        else {
+            return Err(PacketRun {
+                msg: Some("Character creation by not authenticated client".to_string()),
+            });
+        };
+        handler.check_pin_verified(&account).await?;
+        let controller = handler.get_controller().clone();
+        let cfg = controller.get_cfg();
+        let templates = controller.get_char_templates();
+        let template = match self.validate(&templates) {
+            Ok(template) => template,
+            Err(reason) => {
+                handler
+                    .send_packet(Box::new(CharCreateFail::new(reason)?))
+                    .await?;
+                return Ok(());
+            }
+        };
+        let db_pool = handler.get_db_pool_mut().clone();
+        let chars = character::Model::find_characters_by_username(&db_pool, &account).await?;
+        let reason = if chars.len() >= usize::from(cfg.max_chars_on_account) {
+            Some(CharCreateFailReason::TooManyCharacters)
+        } else if character::Model::is_name_taken(&db_pool, &self.name).await? {
+            Some(CharCreateFailReason::NameAlreadyExists)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            handler
+                .send_packet(Box::new(CharCreateFail::new(reason)?))
+                .await?;
+            return Ok(());
+        }
+        let user = user::Model::find_by_username(&db_pool, &account).await?;
+        let created = character::Model::create(
+            &db_pool,
+            self.new_character(template, user.id),
+            Self::starting_items(template),
+        )
+        .await;
+        if let Err(err) = created {
+            // the name could be taken by another client since the check above
+            let reason = if let Some(SqlErr::UniqueConstraintViolation(_)) = err.sql_err() {
+                CharCreateFailReason::NameAlreadyExists
+            } else {
+                error!(
+                    "Failed to create character {} for {account}: {err}",
+                    self.name
+                );
+                CharCreateFailReason::CreationFailed
+            };
+            handler
+                .send_packet(Box::new(CharCreateFail::new(reason)?))
+                .await?;
+            return Ok(());
+        }
+        handler.send_packet(Box::new(CharCreateOk::new()?)).await?;
+        handler.notify_chars_changed().await;
+        handler.send_char_selection_info().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Controller;
+    use crate::tests::{read_packet, test_client};
+    use l2_core::config::gs::GSServer;
+    use l2_core::game_objects::paper_doll::PaperDoll;
+    use l2_core::tests::setup_test_db;
+    use l2_core::traits::ServerConfig;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use std::sync::Arc;
+    use tokio::net::TcpStream;
+
+    fn request(name: &str, class_id: i32, race_id: i32) -> CharacterCreate {
+        CharacterCreate {
+            name: name.to_string(),
+            race_id,
+            sex: 1,
+            class_id,
+            hair_style: 5,
+            hair_color: 0,
+            face: 2,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let templates = CharTemplates::load("../config/char_templates.yaml");
+        assert_eq!(
+            request("Bob", 10, 0).validate(&templates).unwrap().class_id,
+            10
+        );
+        assert_eq!(
+            request("Bob the 1st", 10, 0)
+                .validate(&templates)
+                .unwrap_err(),
+            CharCreateFailReason::IncorrectName
+        );
+        assert_eq!(
+            request(&"a".repeat(17), 10, 0)
+                .validate(&templates)
+                .unwrap_err(),
+            CharCreateFailReason::Name16EngChars
+        );
+        // class of another race
+        assert_eq!(
+            request("Bob", 10, 1).validate(&templates).unwrap_err(),
+            CharCreateFailReason::CreationFailed
+        );
+        let mut male = request("Bob", 10, 0);
+        male.sex = 0;
+        assert_eq!(
+            male.validate(&templates).unwrap_err(),
+            CharCreateFailReason::CreationFailed
+        );
+    }
+
+    async fn create_fail_reason(client: &mut TcpStream) -> i32 {
+        let packet = read_packet(client).await;
+        assert_eq!(packet[0], 0x10);
+        i32::from_le_bytes(packet[1..5].try_into().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_create_character() {
+        let mut cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        cfg.max_chars_on_account = 2;
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let (mut handler, mut client) = test_client(db_pool.clone(), controller).await;
+        handler.set_status(ClientStatus::Authenticated);
+        handler.account_name = Some("admin".to_string());
+        handler.set_pin_verified();
+
+        request("Bob", 10, 0).handle(&mut handler).await.unwrap();
+        assert_eq!(read_packet(&mut client).await[0], 0x0F);
+        read_packet(&mut client).await; // the new character list
+        let chars = character::Model::find_characters_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        assert_eq!(chars.len(), 1);
+        let bob = &chars[0];
+        assert_eq!(
+            (bob.name.as_str(), bob.level, bob.class_id),
+            ("Bob", 1, Some(10))
+        );
+        assert_eq!((bob.max_hp, bob.max_mp, bob.max_cp), (101.0, 40.0, 50.5));
+        assert_eq!((bob.x, bob.y, bob.z), (-90875, 248_162, -3570));
+        let items = item::Entity::find()
+            .filter(item::Column::Owner.eq(bob.id))
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().any(|i| i.item_id == 6
+            && i.loc == ItemLocation::Paperdoll
+            && i.loc_data == i32::from(PaperDoll::RHand as u8)));
+        assert!(items
+            .iter()
+            .any(|i| i.item_id == 5588 && i.loc == ItemLocation::Inventory));
+
+        request("bOB", 0, 0).handle(&mut handler).await.unwrap();
+        assert_eq!(
+            create_fail_reason(&mut client).await,
+            CharCreateFailReason::NameAlreadyExists as i32
+        );
+
+        request("Alice", 0, 0).handle(&mut handler).await.unwrap();
+        assert_eq!(read_packet(&mut client).await[0], 0x0F);
+        read_packet(&mut client).await;
+        request("Carol", 0, 0).handle(&mut handler).await.unwrap();
+        assert_eq!(
+            create_fail_reason(&mut client).await,
+            CharCreateFailReason::TooManyCharacters as i32
+        );
+        let chars = character::Model::find_characters_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        assert_eq!(chars.len(), 2);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::client_thread::ClientHandler;
+use crate::packets::to_client::{CharDeleteFail, CharDeleteFailReason, CharDeleteSuccess};
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use chrono::{TimeDelta, Utc};
+use entities::entities::character;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::error;
+
+#[derive(Debug, Clone)]
+pub struct CharacterDelete {
+    pub char_slot: i32,
+}
+
+impl ReadablePacket for CharacterDelete {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        let char_slot = buffer.read_i32();
+        Some(Self { char_slot })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for CharacterDelete {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let character = match handler.get_char_by_slot(self.char_slot).await {
+            Ok(character) => character,
+            Err(err) => {
+                error!("Can not delete character: {err}");
+                handler
+                    .send_packet(Box::new(CharDeleteFail::new(
+                        CharDeleteFailReason::DeletionFailed,
+                    )?))
+                    .await?;
+                return Ok(());
+            }
+        };
+        if character.delete_at.is_some() {
+            // the deletion time must not move, the client can ask again after a restore
+            handler
+                .send_packet(Box::new(CharDeleteFail::new(
+                    CharDeleteFailReason::DeletionFailed,
+                )?))
+                .await?;
+            return Ok(());
+        }
+        // todo: clan leaders can't be deleted and members must leave the clan first,
+        //  check it with ClanLeadersMayNotBeDeleted/YouMayNotDeleteClanMember once clans exist
+        let delay_days = handler.get_controller().get_cfg().char_deletion.delay_days;
+        let db_pool = handler.get_db_pool_mut().clone();
+        if delay_days == 0 {
+            character::Model::delete_with_items(&db_pool, &[character.id]).await?;
+        } else {
+            let delete_at = Utc::now() + TimeDelta::days(i64::from(delay_days));
+            character
+                .set_delete_at(&db_pool, Some(delete_at.fixed_offset()))
+                .await?;
+        }
+        handler
+            .send_packet(Box::new(CharDeleteSuccess::new()?))
+            .await?;
+        handler.notify_chars_changed().await;
+        handler.send_char_selection_info().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client_thread::ClientStatus;
+    use crate::controller::Controller;
+    use crate::ls_thread::LoginHandler;
+    use crate::tests::{connected_pair, test_client};
+    use entities::entities::user;
+    use l2_core::config::gs::GSServer;
+    use l2_core::tests::{new_char, setup_test_db};
+    use l2_core::traits::ServerConfig;
+    use sea_orm::EntityTrait;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn test_delete_twice_keeps_deletion_time() {
+        let cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        assert!(cfg.char_deletion.delay_days > 0);
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        let (ls_stream, mut fake_ls) = connected_pair().await;
+        let lh = LoginHandler::new(ls_stream, db_pool.clone(), controller.clone());
+        controller
+            .message_broker
+            .register_packet_handler(LoginHandler::HANDLER_ID, Arc::new(lh));
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let char_model = character::Model::create(&db_pool, new_char("Bob", user.id), vec![])
+            .await
+            .unwrap();
+        let (mut handler, _client) = test_client(db_pool.clone(), controller).await;
+        handler.set_status(ClientStatus::Authenticated);
+        handler.account_name = Some("admin".to_string());
+        handler.set_pin_verified();
+
+        let delete = CharacterDelete { char_slot: 0 };
+        delete.handle(&mut handler).await.unwrap();
+        // the new character counts are sent to login server
+        let mut buf = [0; 2];
+        tokio::time::timeout(Duration::from_secs(1), fake_ls.read_exact(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        let find_char = || character::Entity::find_by_id(char_model.id).one(&db_pool);
+        let delete_at = find_char().await.unwrap().unwrap().delete_at;
+        assert!(delete_at.is_some());
+
+        delete.handle(&mut handler).await.unwrap();
+        assert_eq!(find_char().await.unwrap().unwrap().delete_at, delete_at);
+    }
+}
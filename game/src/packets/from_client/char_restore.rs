@@ -0,0 +1,37 @@
+use crate::client_thread::ClientHandler;
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::PacketHandler;
+
+/// Cancels the deletion of a character while its deletion timer is running.
+#[derive(Debug, Clone)]
+pub struct CharacterRestore {
+    pub char_slot: i32,
+}
+
+impl ReadablePacket for CharacterRestore {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        let char_slot = buffer.read_i32();
+        Some(Self { char_slot })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for CharacterRestore {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let character = handler.get_char_by_slot(self.char_slot).await?;
+        if character.delete_at.is_some() {
+            let db_pool = handler.get_db_pool_mut().clone();
+            character.set_delete_at(&db_pool, None).await?;
+            handler.notify_chars_changed().await;
+        }
+        handler.send_char_selection_info().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::packets::to_client::{CharSelected, CharSelectionInfo};
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use chrono::Utc;
+use l2_core::game_objects::player::Player;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::info;
+
+/// The player picked a character on the character selection screen.
+#[derive(Debug, Clone)]
+pub struct CharacterSelect {
+    pub char_slot: i32,
+}
+
+impl ReadablePacket for CharacterSelect {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        let char_slot = buffer.read_i32();
+        Some(Self { char_slot })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for CharacterSelect {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let controller = handler.get_controller().clone();
+        let char_model = handler.get_char_by_slot(self.char_slot).await?;
+        if char_model.delete_at.is_some() {
+            return Err(PacketRun {
+                msg: Some(format!("Character {} is pending deletion", char_model.name)),
+            });
+        }
+        if char_model.access_level == Some(CharSelectionInfo::BLOCKED_ACCESS_LEVEL) {
+            return Err(PacketRun {
+                msg: Some(format!("Character {} is blocked", char_model.name)),
+            });
+        }
+        let Some(account) = handler.account_name.clone() else {
+            return Err(PacketRun {
+                msg: Some("Character selected by not authenticated client".to_string()),
+            });
+        };
+        let db_pool = handler.get_db_pool_mut().clone();
+        let char_model = char_model
+            .set_last_access(&db_pool, Utc::now().fixed_offset())
+            .await?;
+        let player = Player::load(&db_pool, char_model, &controller.get_char_templates()).await?;
+        info!("{account} selected character {}", player.char_model.name);
+        let packet = CharSelected::new(&player, handler.get_session_id())?;
+        controller.add_player(&account, player);
+        handler.set_status(ClientStatus::Entering);
+        handler.send_packet(Box::new(packet)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Controller;
+    use crate::tests::test_client;
+    use entities::entities::{character, user};
+    use l2_core::config::gs::GSServer;
+    use l2_core::hash_password;
+    use l2_core::tests::{new_char, setup_test_db};
+    use l2_core::traits::ServerConfig;
+    use sea_orm::EntityTrait;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_select_requires_pin() {
+        let cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        assert!(!cfg.secondary_auth.required);
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let user_id = user.id;
+        let pin_hash = hash_password("135790").await.unwrap();
+        user.set_pin_hash(&db_pool, Some(pin_hash)).await.unwrap();
+        let char_model = character::Model::create(&db_pool, new_char("Bob", user_id), vec![])
+            .await
+            .unwrap();
+        assert!(char_model.last_access.is_none());
+        let (mut handler, _client) = test_client(db_pool.clone(), controller.clone()).await;
+        handler.set_status(ClientStatus::Authenticated);
+        handler.account_name = Some("admin".to_string());
+
+        let select = CharacterSelect { char_slot: 0 };
+        assert!(select.handle(&mut handler).await.is_err());
+        assert!(controller.with_player("admin", |_| ()).is_none());
+
+        handler.set_pin_verified();
+        select.handle(&mut handler).await.unwrap();
+        assert_eq!(handler.get_status(), &ClientStatus::Entering);
+        assert!(controller.with_player("admin", |_| ()).is_some());
+        let char_model = character::Entity::find_by_id(char_model.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(char_model.last_access.is_some());
+    }
+}
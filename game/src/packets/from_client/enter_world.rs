@@ -0,0 +1,53 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::packets::to_client::{ExBasicActionList, ItemList, SkillList, UserInfo};
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use l2_core::packets::common::{ReadablePacket, SendablePacket};
+use l2_core::packets::error::PacketRun;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::info;
+
+/// Sent when the client finished loading the world after `CharSelected`.
+#[derive(Debug, Clone)]
+pub struct EnterWorld;
+
+impl ReadablePacket for EnterWorld {
+    fn read(_: &[u8]) -> Option<Self> {
+        // the rest of the packet is a tracert of the client, it isn't used
+        Some(Self)
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for EnterWorld {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let (ClientStatus::Entering, Some(account)) =
+            (handler.get_status(), handler.account_name.clone())
+        else {
+            return Err(PacketRun {
+                msg: Some("Entering the world without a selected character".to_string()),
+            });
+        };
+        let packets = handler
+            .get_controller()
+            .with_player(&account, |player| -> anyhow::Result<_> {
+                let packets: Vec<Box<dyn SendablePacket>> = vec![
+                    Box::new(UserInfo::new(player)?),
+                    Box::new(ExBasicActionList::new()?),
+                    Box::new(SkillList::new()?),
+                    Box::new(ItemList::new(player, false)?),
+                ];
+                Ok(packets)
+            })
+            .ok_or_else(|| PacketRun {
+                msg: Some(format!("No character selected for {account}")),
+            })??;
+        handler.set_status(ClientStatus::InGame);
+        for packet in packets {
+            handler.send_packet(packet).await?;
+        }
+        info!("{account} entered the world");
+        Ok(())
+    }
+}
@@ -1,2 +1,10 @@
 pub mod protocol;
-mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod secondary_auth;
+pub mod new_character;
+pub mod char_create;
+pub mod char_delete;
+pub mod char_restore;
+pub mod char_select;
+pub mod enter_world;
+pub mod say;
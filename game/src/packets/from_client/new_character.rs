@@ -0,0 +1,34 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::packets::to_client::NewCharacterSuccess;
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+
+/// Sent when the player opens the character creation screen.
+#[derive(Debug, Clone)]
+pub struct RequestNewCharacter;
+
+impl ReadablePacket for RequestNewCharacter {
+    fn read(_: &[u8]) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestNewCharacter {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        if handler.get_status() != &ClientStatus::Authenticated {
+            return Err(PacketRun {
+                msg: Some("Character creation requested by not authenticated client".to_string()),
+            });
+        }
+        let templates = handler.get_controller().get_char_templates();
+        handler
+            .send_packet(Box::new(NewCharacterSuccess::new(templates.all())?))
+            .await?;
+        Ok(())
+    }
+}
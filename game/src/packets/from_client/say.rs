@@ -0,0 +1,160 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::ls_thread::LoginHandler;
+use crate::packets::to_client::SystemMessage;
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::gs_2_ls::ChangePassword;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::error;
+
+/// A chat message of the player. There is no world to broadcast it to yet, only voiced
+/// commands (`.command args`) are handled.
+#[derive(Debug, Clone)]
+pub struct Say2 {
+    pub text: String,
+}
+
+impl Say2 {
+    const CHANGE_PASSWORD_USAGE: &'static str =
+        "Usage: .changepassword <current password> <new password> <repeat new password>";
+
+    /// Checks `.changepassword` arguments, the error is the message for the player.
+    fn parse_change_password(args: &[&str]) -> Result<(String, String), &'static str> {
+        let [current, new, repeat] = args else {
+            return Err(Self::CHANGE_PASSWORD_USAGE);
+        };
+        if new != repeat {
+            return Err("The new passwords do not match");
+        }
+        Ok(((*current).to_string(), (*new).to_string()))
+    }
+
+    async fn change_password(
+        handler: &mut ClientHandler,
+        account: &str,
+        args: &[&str],
+    ) -> anyhow::Result<()> {
+        let (current, new) = match Self::parse_change_password(args) {
+            Ok(passwords) => passwords,
+            Err(msg) => {
+                return handler
+                    .send_packet(Box::new(SystemMessage::text(msg)?))
+                    .await
+            }
+        };
+        let controller = handler.get_controller().clone();
+        let char_name = controller
+            .with_player(account, |player| player.char_model.name.clone())
+            .unwrap_or_default();
+        let request = ChangePassword::new(account, &char_name, &current, &new)?;
+        if let Err(e) = controller
+            .message_broker
+            .notify(LoginHandler::HANDLER_ID, Box::new(request))
+            .await
+        {
+            error!("Can't send password change of {account} to login server: {e}");
+            let msg = SystemMessage::text("The password can't be changed now, try again later")?;
+            handler.send_packet(Box::new(msg)).await?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadablePacket for Say2 {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        let text = buffer.read_string();
+        // the chat type and the whisper target follow, they aren't used without a world
+        Some(Self { text })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for Say2 {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let (ClientStatus::InGame, Some(account)) =
+            (handler.get_status(), handler.account_name.clone())
+        else {
+            return Err(PacketRun {
+                msg: Some("Chat message from a client which is not in game".to_string()),
+            });
+        };
+        let Some(command) = self.text.strip_prefix('.') else {
+            return Ok(());
+        };
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        if name.eq_ignore_ascii_case("changepassword") {
+            Self::change_password(handler, &account, &args).await?;
+        } else {
+            handler
+                .send_packet(Box::new(SystemMessage::text(&format!(
+                    "Unknown command: .{name}"
+                ))?))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Controller;
+    use crate::tests::{connected_pair, test_client, test_player};
+    use l2_core::config::gs::GSServer;
+    use l2_core::tests::setup_test_db;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn test_parse_change_password() {
+        assert_eq!(
+            Say2::parse_change_password(&["old", "new", "new"]),
+            Ok(("old".to_string(), "new".to_string()))
+        );
+        assert_eq!(
+            Say2::parse_change_password(&["old", "new", "other"]),
+            Err("The new passwords do not match")
+        );
+        assert_eq!(
+            Say2::parse_change_password(&["old", "new"]),
+            Err(Say2::CHANGE_PASSWORD_USAGE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_password_is_sent_to_login_server() {
+        let cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        let (ls_stream, mut fake_ls) = connected_pair().await;
+        let lh = LoginHandler::new(ls_stream, db_pool.clone(), controller.clone());
+        controller
+            .message_broker
+            .register_packet_handler(LoginHandler::HANDLER_ID, Arc::new(lh));
+        controller.add_player("admin", test_player());
+        let (mut handler, _client) = test_client(db_pool, controller).await;
+        handler.account_name = Some("admin".to_string());
+
+        let say = Say2 {
+            text: ".changepassword old new new".to_string(),
+        };
+        assert!(say.handle(&mut handler).await.is_err());
+        handler.set_status(ClientStatus::InGame);
+        say.handle(&mut handler).await.unwrap();
+        let mut buf = [0; 2];
+        tokio::time::timeout(Duration::from_secs(1), fake_ls.read_exact(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -0,0 +1,371 @@
+use crate::client_thread::{ClientHandler, ClientStatus};
+use crate::packets::to_client::{Ex2ndPasswordAck, Ex2ndPasswordCheck, Ex2ndPasswordVerify};
+use crate::packets::HandleablePacket;
+use async_trait::async_trait;
+use entities::entities::user;
+use l2_core::hash_password;
+use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+
+/// The PIN is asked only between `AuthLogin` and character selection.
+async fn find_authenticated_user(handler: &mut ClientHandler) -> Result<user::Model, PacketRun> {
+    let account = match (handler.get_status(), &handler.account_name) {
+        (ClientStatus::Authenticated, Some(account)) => account.clone(),
+        _ => {
+            return Err(PacketRun {
+                msg: Some("Secondary password requested by not authenticated client".to_string()),
+            })
+        }
+    };
+    Ok(user::Model::find_by_username(handler.get_db_pool_mut(), &account).await?)
+}
+
+/// Checks the PIN counting wrong attempts, on failure the client is told how many attempts
+/// were wrong, or that the account is locked (then the connection is closed).
+async fn check_pin(
+    handler: &mut ClientHandler,
+    user: &user::Model,
+    pin: &str,
+) -> Result<bool, PacketRun> {
+    let controller = handler.get_controller().clone();
+    let locked_err = || PacketRun {
+        msg: Some(format!(
+            "Account {} is locked, too many wrong PINs",
+            user.username
+        )),
+    };
+    if controller.is_pin_locked(&user.username) {
+        handler
+            .send_packet(Box::new(Ex2ndPasswordVerify::new(
+                Ex2ndPasswordVerify::PASSWORD_BAN,
+                0,
+            )?))
+            .await?;
+        return Err(locked_err());
+    }
+    if user.verify_pin(pin).await {
+        controller.on_pin_succeeded(&user.username);
+        return Ok(true);
+    }
+    let attempts = controller.on_pin_failed(&user.username);
+    if controller.is_pin_locked(&user.username) {
+        handler
+            .send_packet(Box::new(Ex2ndPasswordVerify::new(
+                Ex2ndPasswordVerify::PASSWORD_BAN,
+                attempts,
+            )?))
+            .await?;
+        return Err(locked_err());
+    }
+    handler
+        .send_packet(Box::new(Ex2ndPasswordVerify::new(
+            Ex2ndPasswordVerify::PASSWORD_WRONG,
+            attempts,
+        )?))
+        .await?;
+    Ok(false)
+}
+
+/// Sent by the client when the character selection window opens.
+#[derive(Debug, Clone)]
+pub struct RequestEx2ndPasswordCheck;
+
+impl ReadablePacket for RequestEx2ndPasswordCheck {
+    fn read(_: &[u8]) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestEx2ndPasswordCheck {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let user = find_authenticated_user(handler).await?;
+        let required = handler.get_controller().get_cfg().secondary_auth.required;
+        let window_type = if handler.is_pin_verified() {
+            Ex2ndPasswordCheck::PASSWORD_OK
+        } else if user.pin.is_some() {
+            Ex2ndPasswordCheck::PASSWORD_PROMPT
+        } else if required {
+            Ex2ndPasswordCheck::PASSWORD_NEW
+        } else {
+            handler.set_pin_verified();
+            Ex2ndPasswordCheck::PASSWORD_OK
+        };
+        handler
+            .send_packet(Box::new(Ex2ndPasswordCheck::new(window_type)?))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestEx2ndPasswordVerify {
+    pub pin: String,
+}
+
+impl ReadablePacket for RequestEx2ndPasswordVerify {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        buffer.read_u16(); // ex packet_id
+        let pin = buffer.read_string();
+        Some(Self { pin })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestEx2ndPasswordVerify {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let user = find_authenticated_user(handler).await?;
+        if user.pin.is_none() {
+            return Err(PacketRun {
+                msg: Some(format!("Account {} has no PIN to verify", user.username)),
+            });
+        }
+        if check_pin(handler, &user, &self.pin).await? {
+            handler.set_pin_verified();
+            handler
+                .send_packet(Box::new(Ex2ndPasswordVerify::new(
+                    Ex2ndPasswordVerify::PASSWORD_OK,
+                    0,
+                )?))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a new PIN or changes the existing one (then `pin` is the old one).
+#[derive(Debug, Clone)]
+pub struct RequestEx2ndPasswordReq {
+    pub pin: String,
+    pub new_pin: Option<String>,
+}
+
+impl ReadablePacket for RequestEx2ndPasswordReq {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte(); // packet_id
+        buffer.read_u16(); // ex packet_id
+        let change_pin = buffer.read_byte() == 2;
+        let pin = buffer.read_string();
+        let new_pin = change_pin.then(|| buffer.read_string());
+        Some(Self { pin, new_pin })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestEx2ndPasswordReq {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, handler: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let user = find_authenticated_user(handler).await?;
+        let new_pin = match (&self.new_pin, &user.pin) {
+            (None, None) => &self.pin,
+            (Some(new_pin), Some(_)) => {
+                if !check_pin(handler, &user, &self.pin).await? {
+                    return Ok(());
+                }
+                new_pin
+            }
+            _ => {
+                return Err(PacketRun {
+                    msg: Some(format!("Unexpected PIN request for {}", user.username)),
+                })
+            }
+        };
+        if !user::Model::is_valid_pin(new_pin) {
+            handler
+                .send_packet(Box::new(Ex2ndPasswordAck::new(
+                    Ex2ndPasswordAck::WRONG_PATTERN,
+                )?))
+                .await?;
+            return Ok(());
+        }
+        let pin_hash = hash_password(new_pin).await?;
+        user.set_pin_hash(handler.get_db_pool_mut(), Some(pin_hash))
+            .await?;
+        handler.set_pin_verified();
+        handler
+            .send_packet(Box::new(Ex2ndPasswordAck::new(Ex2ndPasswordAck::SUCCESS)?))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Controller;
+    use crate::tests::{read_packet, test_client};
+    use l2_core::config::gs::GSServer;
+    use l2_core::tests::{setup_test_db, DBPool};
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use tokio::net::TcpStream;
+
+    /// An authenticated client of "admin", the account has the PIN if it is given.
+    async fn pin_client(cfg: GSServer, pin: Option<&str>) -> (ClientHandler, TcpStream, DBPool) {
+        let controller = Arc::new(Controller::new(Arc::new(cfg)));
+        let db_pool = setup_test_db().await;
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        if let Some(pin) = pin {
+            let pin_hash = hash_password(pin).await.unwrap();
+            user.set_pin_hash(&db_pool, Some(pin_hash)).await.unwrap();
+        }
+        let (mut handler, client) = test_client(db_pool.clone(), controller).await;
+        handler.set_status(ClientStatus::Authenticated);
+        handler.account_name = Some("admin".to_string());
+        (handler, client, db_pool)
+    }
+
+    fn test_config() -> GSServer {
+        GSServer::from_string(include_str!("../../test_data/test_config.yaml"))
+    }
+
+    fn read_u32(packet: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(packet[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Mode and wrong attempts of `Ex2ndPasswordVerify`.
+    async fn read_verify(client: &mut TcpStream) -> (u32, u32) {
+        let packet = read_packet(client).await;
+        assert_eq!(&packet[..3], &[0xFE, 0x06, 0x01]);
+        (read_u32(&packet, 3), read_u32(&packet, 7))
+    }
+
+    async fn read_ack(client: &mut TcpStream) -> u32 {
+        let packet = read_packet(client).await;
+        assert_eq!(&packet[..3], &[0xFE, 0x07, 0x01]);
+        read_u32(&packet, 4)
+    }
+
+    async fn read_check(client: &mut TcpStream) -> u32 {
+        let packet = read_packet(client).await;
+        assert_eq!(&packet[..3], &[0xFE, 0x05, 0x01]);
+        read_u32(&packet, 3)
+    }
+
+    #[tokio::test]
+    async fn test_check_window() {
+        let (mut handler, mut client, _db) = pin_client(test_config(), Some("135790")).await;
+        RequestEx2ndPasswordCheck
+            .handle(&mut handler)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_check(&mut client).await,
+            Ex2ndPasswordCheck::PASSWORD_PROMPT
+        );
+        assert!(!handler.is_pin_verified());
+
+        let mut cfg = test_config();
+        cfg.secondary_auth.required = true;
+        let (mut handler, mut client, _db) = pin_client(cfg, None).await;
+        RequestEx2ndPasswordCheck
+            .handle(&mut handler)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_check(&mut client).await,
+            Ex2ndPasswordCheck::PASSWORD_NEW
+        );
+
+        let (mut handler, mut client, _db) = pin_client(test_config(), None).await;
+        RequestEx2ndPasswordCheck
+            .handle(&mut handler)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_check(&mut client).await,
+            Ex2ndPasswordCheck::PASSWORD_OK
+        );
+        assert!(handler.is_pin_verified());
+    }
+
+    #[tokio::test]
+    async fn test_verify_locks_after_max_attempts() {
+        let mut cfg = test_config();
+        cfg.secondary_auth.max_attempts = 3;
+        let (mut handler, mut client, _db) = pin_client(cfg, Some("135790")).await;
+        let verify = |pin: &str| RequestEx2ndPasswordVerify {
+            pin: pin.to_string(),
+        };
+        for attempt in 1..3 {
+            verify("111222").handle(&mut handler).await.unwrap();
+            assert_eq!(
+                read_verify(&mut client).await,
+                (Ex2ndPasswordVerify::PASSWORD_WRONG, attempt)
+            );
+        }
+        // the connection is closed when the account gets locked
+        assert!(verify("111222").handle(&mut handler).await.is_err());
+        assert_eq!(
+            read_verify(&mut client).await,
+            (Ex2ndPasswordVerify::PASSWORD_BAN, 3)
+        );
+        assert!(verify("135790").handle(&mut handler).await.is_err());
+        assert_eq!(
+            read_verify(&mut client).await,
+            (Ex2ndPasswordVerify::PASSWORD_BAN, 0)
+        );
+        assert!(!handler.is_pin_verified());
+    }
+
+    #[tokio::test]
+    async fn test_verify_pin() {
+        let (mut handler, mut client, _db) = pin_client(test_config(), Some("135790")).await;
+        let verify = RequestEx2ndPasswordVerify {
+            pin: "135790".to_string(),
+        };
+        verify.handle(&mut handler).await.unwrap();
+        assert_eq!(
+            read_verify(&mut client).await,
+            (Ex2ndPasswordVerify::PASSWORD_OK, 0)
+        );
+        assert!(handler.is_pin_verified());
+    }
+
+    #[tokio::test]
+    async fn test_create_and_change_pin() {
+        let (mut handler, mut client, db_pool) = pin_client(test_config(), None).await;
+        let request = |pin: &str, new_pin: Option<&str>| RequestEx2ndPasswordReq {
+            pin: pin.to_string(),
+            new_pin: new_pin.map(str::to_string),
+        };
+        let find_user = || user::Model::find_by_username(&db_pool, "admin");
+
+        request("111111", None).handle(&mut handler).await.unwrap();
+        assert_eq!(read_ack(&mut client).await, Ex2ndPasswordAck::WRONG_PATTERN);
+        assert!(find_user().await.unwrap().pin.is_none());
+        assert!(!handler.is_pin_verified());
+
+        request("135790", None).handle(&mut handler).await.unwrap();
+        assert_eq!(read_ack(&mut client).await, Ex2ndPasswordAck::SUCCESS);
+        assert!(find_user().await.unwrap().verify_pin("135790").await);
+        assert!(handler.is_pin_verified());
+
+        // changing the PIN needs the old one
+        request("000000", Some("246802"))
+            .handle(&mut handler)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_verify(&mut client).await,
+            (Ex2ndPasswordVerify::PASSWORD_WRONG, 1)
+        );
+        assert!(find_user().await.unwrap().verify_pin("135790").await);
+
+        request("135790", Some("246802"))
+            .handle(&mut handler)
+            .await
+            .unwrap();
+        assert_eq!(read_ack(&mut client).await, Ex2ndPasswordAck::SUCCESS);
+        assert!(find_user().await.unwrap().verify_pin("246802").await);
+    }
+}
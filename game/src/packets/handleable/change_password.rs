@@ -1,12 +1,39 @@
-use l2_core::packets::{error::PacketRun, gs_2_ls::ChangePassword};
+use l2_core::packets::{error::PacketRun, ls_2_gs::ChangePassword};
 use async_trait::async_trait;
+use entities::entities::character;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::{error, info};
 use crate::ls_thread::LoginHandler;
+use crate::packets::to_client::SystemMessage;
 use crate::packets::HandleablePacket;
 
 #[async_trait]
 impl HandleablePacket for ChangePassword {
     type HandlerType = LoginHandler;
-    async fn handle(&self, _: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        todo!()
+    async fn handle(&self, lh: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let controller = lh.get_controller().clone();
+        let db_pool = lh.get_db_pool_mut();
+        let account = match character::Model::find_account_name(db_pool, &self.char_name).await {
+            Ok(account) => account,
+            Err(e) => {
+                error!("Can't find account of {}: {e}", self.char_name);
+                return Ok(());
+            }
+        };
+        let Some(client) = account.and_then(|acc| controller.get_client(&acc)) else {
+            info!(
+                "Character {} is offline, password change result dropped: {}",
+                self.char_name, self.message
+            );
+            return Ok(());
+        };
+        let sent = match SystemMessage::text(&self.message) {
+            Ok(msg) => client.send_packet(Box::new(msg)).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            error!("Can't relay password change result to {}: {e}", self.char_name);
+        }
+        Ok(())
     }
 }
@@ -30,7 +30,9 @@ impl HandleablePacket for ls_2_gs::AuthGS {
             "Registered on Login server: {:} ({:})",
             self.server_name, self.server_id
         );
-        controller.message_broker.register_packet_handler(self.server_id, Arc::new(lh.clone()));
+        controller
+            .message_broker
+            .register_packet_handler(LoginHandler::HANDLER_ID, Arc::new(lh.clone()));
         let accounts = controller.get_online_accounts();
         if !accounts.is_empty() {
             lh.send_packet(Box::new(PlayerInGame::new(&accounts)?))
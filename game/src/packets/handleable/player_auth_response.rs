@@ -1,13 +1,101 @@
 use async_trait::async_trait;
+use l2_core::packets::common::PacketType;
 use l2_core::packets::ls_2_gs;
 use l2_core::packets::error::PacketRun;
+use l2_core::traits::handlers::PacketHandler;
 use crate::ls_thread::LoginHandler;
 use crate::packets::HandleablePacket;
 
 #[async_trait]
 impl HandleablePacket for ls_2_gs::PlayerAuthResponse {
     type HandlerType = LoginHandler;
-    async fn handle(&self, _: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        todo!()
+    async fn handle(&self, lh: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        // the client thread waits for it in AuthLogin, the message is keyed by account name
+        lh.get_controller().message_broker.respond_to_message(
+            Some(LoginHandler::HANDLER_ID),
+            &self.account,
+            PacketType::PlayerAuthResp(self.clone()),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::controller::Controller;
+    use crate::ls_thread::LoginHandler;
+    use l2_core::config::gs::GSServer;
+    use l2_core::crypt::login::Encryption;
+    use l2_core::packets::common::PacketType;
+    use l2_core::packets::gs_2_ls::PlayerAuthRequest;
+    use l2_core::packets::ls_2_gs::PlayerAuthResponse;
+    use l2_core::session::SessionKey;
+    use l2_core::tests::setup_test_db;
+    use l2_core::traits::handlers::PacketHandler;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Connects a game server `LoginHandler` to a fake login server, which answers the
+    /// `PlayerAuthRequest` with the given result.
+    async fn authenticate(account: &str, is_ok: bool) -> Option<PlayerAuthResponse> {
+        let cfg = Arc::new(GSServer::from_string(include_str!(
+            "../../test_data/test_config.yaml"
+        )));
+        let controller = Arc::new(Controller::new(cfg.clone()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut fake_ls, _) = listener.accept().await.unwrap();
+        let mut lh = LoginHandler::new(stream, setup_test_db().await, controller.clone());
+        controller
+            .message_broker
+            .register_packet_handler(LoginHandler::HANDLER_ID, Arc::new(lh.clone()));
+        tokio::spawn(async move { lh.handle_client().await });
+
+        let acc = account.to_string();
+        let broker = controller.message_broker.clone();
+        let request = tokio::spawn(async move {
+            let packet = PlayerAuthRequest::new(&acc, SessionKey::new()).unwrap();
+            broker
+                .send_message(LoginHandler::HANDLER_ID, &acc, Box::new(packet))
+                .await
+        });
+
+        let mut size = [0u8; 2];
+        fake_ls.read_exact(&mut size).await.unwrap();
+        let mut body = vec![0u8; usize::from(u16::from_le_bytes(size)) - 2];
+        fake_ls.read_exact(&mut body).await.unwrap();
+        let blowfish = Encryption::from_u8_key(cfg.blowfish_key.as_bytes());
+        blowfish.decrypt(&mut body).unwrap();
+        assert_eq!(body[0], 0x05, "PlayerAuthRequest expected");
+
+        let mut response = PlayerAuthResponse::new(account, is_ok);
+        response.buffer.write_padding().unwrap();
+        let mut bytes = response.buffer.get_data();
+        Encryption::append_checksum(&mut bytes[2..]);
+        blowfish.encrypt(&mut bytes[2..]);
+        fake_ls.write_all(&bytes).await.unwrap();
+
+        match request.await.unwrap().unwrap() {
+            Some((LoginHandler::HANDLER_ID, PacketType::PlayerAuthResp(p))) => Some(p),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_accepted() {
+        let response = authenticate("admin", true).await.unwrap();
+        assert_eq!(response.account, "admin");
+        assert!(response.is_ok);
+    }
+
+    #[tokio::test]
+    async fn test_auth_rejected() {
+        let response = authenticate("intruder", false).await.unwrap();
+        assert_eq!(response.account, "intruder");
+        assert!(!response.is_ok);
     }
 }
@@ -0,0 +1,36 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Actions shown in the action window (sit, run, trade, social actions, etc.).
+#[derive(Debug, Clone)]
+pub struct ExBasicActionList {
+    buffer: SendablePacketBuffer,
+}
+
+impl ExBasicActionList {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_ID: u16 = 0x60;
+
+    /// General and social actions, pet and summon ones (1000+) are added once there are summons.
+    fn default_actions() -> impl Iterator<Item = i32> {
+        (0..=74).chain(76..=90)
+    }
+
+    pub fn new() -> anyhow::Result<Self> {
+        let actions: Vec<i32> = Self::default_actions().collect();
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_u16(Self::EX_ID)?;
+        buffer.write_i32(i32::try_from(actions.len())?)?;
+        for action in actions {
+            buffer.write_i32(action)?;
+        }
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for ExBasicActionList {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,59 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+#[derive(Debug, Clone)]
+pub struct CharCreateOk {
+    buffer: SendablePacketBuffer,
+}
+
+impl CharCreateOk {
+    const PACKET_ID: u8 = 0x0F;
+
+    pub fn new() -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i32(1)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for CharCreateOk {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(unused)]
+pub enum CharCreateFailReason {
+    CreationFailed = 0x00,
+    TooManyCharacters = 0x01,
+    NameAlreadyExists = 0x02,
+    Name16EngChars = 0x03,
+    IncorrectName = 0x04,
+    CreateNotAllowed = 0x05,
+    ChooseAnotherServer = 0x06,
+}
+
+#[derive(Debug, Clone)]
+pub struct CharCreateFail {
+    buffer: SendablePacketBuffer,
+}
+
+impl CharCreateFail {
+    const PACKET_ID: u8 = 0x10;
+
+    pub fn new(reason: CharCreateFailReason) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i32(reason as i32)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for CharCreateFail {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,54 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+#[derive(Debug, Clone)]
+pub struct CharDeleteSuccess {
+    buffer: SendablePacketBuffer,
+}
+
+impl CharDeleteSuccess {
+    const PACKET_ID: u8 = 0x1D;
+
+    pub fn new() -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for CharDeleteSuccess {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(unused)]
+pub enum CharDeleteFailReason {
+    DeletionFailed = 0x01,
+    YouMayNotDeleteClanMember = 0x02,
+    ClanLeadersMayNotBeDeleted = 0x03,
+}
+
+#[derive(Debug, Clone)]
+pub struct CharDeleteFail {
+    buffer: SendablePacketBuffer,
+}
+
+impl CharDeleteFail {
+    const PACKET_ID: u8 = 0x1E;
+
+    pub fn new(reason: CharDeleteFailReason) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i32(reason as i32)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for CharDeleteFail {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,57 @@
+use l2_core::game_objects::player::Player;
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Confirms the chosen character, the client starts loading the world and then sends `EnterWorld`.
+#[derive(Debug, Clone)]
+pub struct CharSelected {
+    buffer: SendablePacketBuffer,
+}
+
+impl CharSelected {
+    const PACKET_ID: u8 = 0x0B;
+    /// Game time in minutes since midnight, there is no game clock yet so it is always noon.
+    const GAME_TIME: i32 = 12 * 60;
+
+    pub fn new(player: &Player, session_id: i32) -> anyhow::Result<Self> {
+        let char_model = &player.char_model;
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_string(Some(&char_model.name))?;
+        buffer.write_i32(player.get_object_id())?;
+        buffer.write_string(char_model.title.as_deref())?;
+        buffer.write_i32(session_id)?;
+        buffer.write_i32(0)?; // clan id
+        buffer.write_i32(0)?;
+        buffer.write_i32(i32::from(char_model.sex))?;
+        buffer.write_i32(i32::from(char_model.race_id))?;
+        buffer.write_i32(i32::from(player.get_class_id()))?;
+        buffer.write_i32(1)?; // active
+        buffer.write_i32(char_model.x)?;
+        buffer.write_i32(char_model.y)?;
+        buffer.write_i32(char_model.z)?;
+        buffer.write_f64(char_model.cur_hp)?;
+        buffer.write_f64(char_model.cur_mp)?;
+        buffer.write_i64(char_model.sp)?;
+        buffer.write_i64(char_model.exp)?;
+        buffer.write_i32(char_model.level)?;
+        buffer.write_i32(char_model.reputation.unwrap_or_default())?;
+        buffer.write_i32(i32::from(char_model.pk_kills.unwrap_or_default()))?;
+        buffer.write_i32(Self::GAME_TIME)?;
+        buffer.write_i32(0)?;
+        buffer.write_i32(i32::from(player.get_class_id()))?;
+        buffer.write_bytes(&[0; 16])?;
+        for _ in 0..9 {
+            buffer.write_i32(0)?;
+        }
+        buffer.write_bytes(&[0; 28])?;
+        buffer.write_i32(0)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for CharSelected {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -1,7 +1,9 @@
 use async_trait::async_trait;
-use entities::entities::character;
+use chrono::{DateTime, Utc};
+use entities::entities::{character, item};
 use entities::DBPool;
 use l2_core::config::gs::GSServer;
+use l2_core::game_objects::paper_doll::PaperDoll;
 use l2_core::packets::common::SendablePacket;
 use l2_core::packets::write::SendablePacketBuffer;
 
@@ -15,18 +17,35 @@ pub struct CharSelectionInfo {
 
 impl CharSelectionInfo {
     const PACKET_ID: u8 = 0x09;
+    /// Exp bonus shown for the vitality, in percent.
+    const VITALITY_BONUS: i32 = 200;
+    /// Characters with this access level are blocked and shown greyed out.
+    pub const BLOCKED_ACCESS_LEVEL: i32 = -100;
 
-    #[allow(clippy::cast_possible_truncation)]
-    #[allow(clippy::cast_possible_wrap)]
     pub async fn new(
         account_name: &str,
         session_id: i32,
         cfg: &GSServer,
         db_pool: &mut DBPool,
+    ) -> anyhow::Result<Self> {
+        let chars = character::Model::find_characters_by_username(db_pool, account_name).await?;
+        let char_ids: Vec<i32> = chars.iter().map(|c| c.id).collect();
+        let items = item::Model::find_paperdoll_items(db_pool, &char_ids).await?;
+        Self::build(account_name, session_id, cfg, &chars, &items, Utc::now())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    fn build(
+        account_name: &str,
+        session_id: i32,
+        cfg: &GSServer,
+        chars: &[character::Model],
+        items: &[item::Model],
+        now: DateTime<Utc>,
     ) -> anyhow::Result<Self> {
         let mut buffer = SendablePacketBuffer::new();
         buffer.write(Self::PACKET_ID)?;
-        let chars = character::Model::find_characters_by_username(db_pool, account_name).await?;
         let char_len = chars.len() as u32;
         buffer.write_u32(char_len)?;
         buffer.write_u32(u32::from(cfg.max_chars_on_account))?;
@@ -34,13 +53,16 @@ impl CharSelectionInfo {
         buffer.write(1)?; // 0=can't play, 1=can play free until level 85, 2=100% free play
         buffer.write_u32(2)?; // if 1, Korean client
         buffer.write(0)?; // Balthus Knights, if 1 suggests premium account
-        let mut last_access = None;
-        let mut active_id = -1;
+
+        // the last played character is preselected
+        let active_id = chars
+            .iter()
+            .enumerate()
+            .filter_map(|(index, char)| char.last_access.map(|last| (index, last)))
+            .max_by_key(|(_, last)| *last)
+            .map_or(-1, |(index, _)| index as i32);
         for (index, char) in chars.iter().enumerate() {
-            if char.last_access > last_access {
-                last_access = char.last_access;
-                active_id = index as i32;
-            }
+            let paper_doll = Self::paper_doll(items, char.id);
             buffer.write_string(Some(&char.name))?;
             buffer.write_i32(char.id)?;
             buffer.write_string(Some(account_name))?;
@@ -50,7 +72,7 @@ impl CharSelectionInfo {
             buffer.write_i32(i32::from(char.sex))?;
             buffer.write_i32(i32::from(char.race_id))?;
             buffer.write_i32(i32::from(char.base_class_id))?;
-            buffer.write_i32(1)?; // GameServerName
+            buffer.write_i32(i32::from(cfg.server_id))?;
             buffer.write_i32(char.x)?;
             buffer.write_i32(char.y)?;
             buffer.write_i32(char.z)?;
@@ -58,7 +80,61 @@ impl CharSelectionInfo {
             buffer.write_f64(char.cur_mp)?;
             buffer.write_i64(char.sp)?;
             buffer.write_i64(char.exp)?;
-            todo!()
+            buffer.write_f64(0.0)?; // exp percent of the current level, needs the exp table
+            buffer.write_i32(char.level)?;
+            buffer.write_i32(char.reputation.unwrap_or_default())?;
+            buffer.write_i32(i32::from(char.pk_kills.unwrap_or_default()))?;
+            buffer.write_i32(i32::from(char.pvp_kills))?;
+            for _ in 0..7 {
+                buffer.write_i32(0)?;
+            }
+            buffer.write_i32(0)?; // Ertheia
+            buffer.write_i32(0)?; // Ertheia
+            for slot in PaperDoll::ORDER {
+                buffer.write_i32(paper_doll[slot.slot()].map_or(0, |item| item.item_id))?;
+            }
+            for _ in PaperDoll::VISUAL_ORDER {
+                buffer.write_i32(0)?; // visual id, appearance change is not supported yet
+            }
+            for slot in [
+                PaperDoll::Chest,
+                PaperDoll::Legs,
+                PaperDoll::Head,
+                PaperDoll::Gloves,
+                PaperDoll::Feet,
+            ] {
+                let enchant = Self::enchant_level(&paper_doll, slot);
+                buffer.write_i16(i16::try_from(enchant).unwrap_or(i16::MAX))?;
+            }
+            buffer.write_i32(i32::from(char.hair_style.unwrap_or_default()))?;
+            buffer.write_i32(i32::from(char.hair_color.unwrap_or_default()))?;
+            buffer.write_i32(i32::from(char.face.unwrap_or_default()))?;
+            buffer.write_f64(char.max_hp)?;
+            buffer.write_f64(char.max_mp)?;
+            let delete_in = char.delete_at.map_or(0, |delete_at| {
+                delete_at.signed_duration_since(now).num_seconds().max(0)
+            });
+            buffer.write_i32(i32::try_from(delete_in).unwrap_or(i32::MAX))?;
+            buffer.write_i32(i32::from(char.class_id.unwrap_or(char.base_class_id)))?;
+            buffer.write_i32_from_bool(index as i32 == active_id)?;
+            let weapon_enchant = Self::enchant_level(&paper_doll, PaperDoll::RHand);
+            buffer.write(u8::try_from(weapon_enchant.min(127)).unwrap_or_default())?;
+            buffer.write_i32(0)?; // augmentation option 1
+            buffer.write_i32(0)?; // augmentation option 2
+            buffer.write_i32(0)?; // transformation is not shown on character selection
+            buffer.write_i32(0)?; // pet npc id
+            buffer.write_i32(0)?; // pet level
+            buffer.write_i32(0)?; // pet food
+            buffer.write_i32(0)?; // pet food level
+            buffer.write_f64(0.0)?; // pet hp
+            buffer.write_f64(0.0)?; // pet mp
+            buffer.write_i32(char.vitality_points)?;
+            buffer.write_i32(Self::VITALITY_BONUS)?;
+            buffer.write_i32(0)?; // vitality items used
+            buffer.write_i32_from_bool(char.access_level != Some(Self::BLOCKED_ACCESS_LEVEL))?;
+            buffer.write_bool(char.nobless.is_some_and(|n| n > 0))?;
+            buffer.write(0)?; // hero glow
+            buffer.write_bool(true)?; // show hair accessory
         }
         Ok(Self {
             buffer,
@@ -66,6 +142,26 @@ impl CharSelectionInfo {
             active_id,
         })
     }
+
+    fn paper_doll(
+        items: &[item::Model],
+        char_id: i32,
+    ) -> [Option<&item::Model>; PaperDoll::TOTAL_SLOTS] {
+        let mut paper_doll = [None; PaperDoll::TOTAL_SLOTS];
+        for item in items.iter().filter(|item| item.owner == char_id) {
+            if let Some(slot) = usize::try_from(item.loc_data)
+                .ok()
+                .and_then(|slot| paper_doll.get_mut(slot))
+            {
+                *slot = Some(item);
+            }
+        }
+        paper_doll
+    }
+
+    fn enchant_level(paper_doll: &[Option<&item::Model>], slot: PaperDoll) -> i32 {
+        paper_doll[slot.slot()].map_or(0, |item| item.enchant_level.max(0))
+    }
 }
 
 #[async_trait]
@@ -74,3 +170,82 @@ impl SendablePacket for CharSelectionInfo {
         &mut self.buffer
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeDelta;
+    use entities::entities::sea_orm_active_enums::ItemLocation;
+    use l2_core::traits::ServerConfig;
+
+    fn test_char(now: DateTime<Utc>) -> character::Model {
+        character::Model {
+            delete_at: Some((now + TimeDelta::seconds(3600)).fixed_offset()),
+            last_access: Some(now.fixed_offset()),
+            ..crate::tests::test_char()
+        }
+    }
+
+    fn equipped(id: i32, item_id: i32, enchant_level: i32, slot: PaperDoll) -> item::Model {
+        item::Model {
+            id,
+            owner: 7,
+            item_id,
+            count: 1,
+            enchant_level,
+            loc: ItemLocation::Paperdoll,
+            loc_data: i32::from(slot as u8),
+        }
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_account() {
+        let cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        let mut packet = CharSelectionInfo::build("admin", 1, &cfg, &[], &[], Utc::now()).unwrap();
+        assert_eq!(packet.active_id, -1);
+        assert_eq!(
+            packet.get_buffer_mut().get_data_mut(),
+            [18, 0, 0x09, 0, 0, 0, 0, 5, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_char_with_equipment() {
+        let cfg = GSServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        let now = Utc::now();
+        let items = [
+            equipped(1, 2369, 3, PaperDoll::RHand),
+            equipped(2, 1146, 1, PaperDoll::Chest),
+        ];
+        let mut packet =
+            CharSelectionInfo::build("admin", 0x1234, &cfg, &[test_char(now)], &items, now)
+                .unwrap();
+        assert_eq!(packet.active_id, 0);
+        // Not a capture of the official server: the bytes were written by this implementation
+        // and only guard against unintended layout changes. Replace them with a real capture
+        // once one is available.
+        // header, then Bob with a +3 weapon (2369) and a +1 chest (1146), deleted in 1h
+        let expected = from_hex(concat!(
+            "c8010901000000050000000001020000000042006f006200000007000000610064006d0069006e00",
+            "00003412000000000000000000000100000000000000000000000100000056e9feffdff00300e0f3",
+            "ffff000000000020594000000000000049400a000000000000004400000000000000000000000000",
+            "00000200000000000000000000000000000000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "00004109000000000000000000007a04000000000000000000000000000041090000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000010000000000000000000100000002000000",
+            "000000000000000000805f400000000000004340100e000000000000010000000300000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000000000000000e02202",
+            "00c80000000000000001000000000001",
+        ));
+        assert_eq!(packet.get_buffer_mut().get_data_mut(), expected.as_slice());
+    }
+}
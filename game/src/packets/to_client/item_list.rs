@@ -0,0 +1,71 @@
+use entities::entities::sea_orm_active_enums::ItemLocation;
+use l2_core::game_objects::player::Player;
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Content of the inventory, equipped items included.
+#[derive(Debug, Clone)]
+pub struct ItemList {
+    buffer: SendablePacketBuffer,
+}
+
+impl ItemList {
+    const PACKET_ID: u8 = 0x11;
+
+    pub fn new(player: &Player, show_window: bool) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i16_from_bool(show_window)?;
+        buffer.write_u16(u16::try_from(player.items.len())?)?;
+        for item in &player.items {
+            let equipped = item.loc == ItemLocation::Paperdoll;
+            buffer.write(0)?; // mask of the optional blocks (augmentation, elementals, etc.)
+            buffer.write_i32(item.id)?;
+            buffer.write_i32(item.item_id)?;
+            buffer.write(if equipped {
+                0xFF
+            } else {
+                u8::try_from(item.loc_data).unwrap_or_default()
+            })?;
+            buffer.write_i64(item.count)?;
+            // todo: item type and body part come from the item data which is not loaded yet
+            buffer.write(0)?; // type 2
+            buffer.write(0)?; // custom type 1
+            buffer.write_i16_from_bool(equipped)?;
+            buffer.write_i64(0)?; // body part
+            buffer.write_i16(i16::try_from(item.enchant_level).unwrap_or(i16::MAX))?;
+            buffer.write(0)?; // custom type 2
+            buffer.write_i32(-1)?; // mana, -1 when the item is not a shadow one
+            buffer.write_i32(-9999)?; // remaining time, -9999 when the item is not a temporary one
+            buffer.write_bool(true)?; // available
+            buffer.write_i16(0)?; // locked
+        }
+        buffer.write_i16(0)?; // blocked items
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for ItemList {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_player;
+
+    #[test]
+    fn test_item_list() {
+        let mut packet = ItemList::new(&test_player(), false).unwrap();
+        let data = packet.get_buffer_mut().get_data();
+        // size, opcode, no window, 2 items of 44 bytes each and no blocked items
+        assert_eq!(data.len(), 2 + 1 + 2 + 2 + 2 * 44 + 2);
+        assert_eq!(data[2..7], [0x11, 0, 0, 2, 0]);
+        // the sword is equipped
+        assert_eq!(data[7..17], [0, 1, 0, 0, 0, 0x41, 0x09, 0, 0, 0xFF]);
+        assert_eq!(data[27..29], [1, 0]);
+        assert_eq!(data[37..39], [3, 0]);
+    }
+}
@@ -1,7 +1,29 @@
 mod protocol_response;
 mod login_response;
 mod char_selection;
+mod server_close;
+mod system_message;
+mod secondary_auth;
+mod new_character;
+mod char_create;
+mod char_delete;
+mod char_selected;
+mod user_info;
+mod item_list;
+mod skill_list;
+mod basic_action_list;
 
 pub use protocol_response::*;
 pub use login_response::*;
-pub use char_selection::*;
\ No newline at end of file
+pub use char_selection::*;
+pub use server_close::*;
+pub use system_message::*;
+pub use secondary_auth::*;
+pub use new_character::*;
+pub use char_create::*;
+pub use char_delete::*;
+pub use char_selected::*;
+pub use user_info::*;
+pub use item_list::*;
+pub use skill_list::*;
+pub use basic_action_list::*;
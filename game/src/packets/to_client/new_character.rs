@@ -0,0 +1,40 @@
+use l2_core::game_objects::char_template::CharTemplate;
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Classes which can be chosen on the character creation screen with their base stats.
+#[derive(Debug, Clone)]
+pub struct NewCharacterSuccess {
+    buffer: SendablePacketBuffer,
+}
+
+impl NewCharacterSuccess {
+    const PACKET_ID: u8 = 0x0D;
+    const MAX_STAT: i32 = 99;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(templates: &[CharTemplate]) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_u32(templates.len() as u32)?;
+        for template in templates {
+            buffer.write_i32(i32::from(template.race_id))?;
+            buffer.write_i32(i32::from(template.class_id))?;
+            let stats = &template.stats;
+            for stat in [
+                stats.str, stats.dex, stats.con, stats.int, stats.wit, stats.men,
+            ] {
+                buffer.write_i32(Self::MAX_STAT)?;
+                buffer.write_i32(stat)?;
+                buffer.write_i32(1)?;
+            }
+        }
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for NewCharacterSuccess {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
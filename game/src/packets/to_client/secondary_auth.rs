@@ -0,0 +1,102 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+const EX_PACKET_ID: u8 = 0xFE;
+
+/// Tells the client which secondary password (PIN) window to show.
+#[derive(Debug, Clone)]
+pub struct Ex2ndPasswordCheck {
+    buffer: SendablePacketBuffer,
+}
+
+impl Ex2ndPasswordCheck {
+    const EX_ID: u16 = 0x105;
+    pub const PASSWORD_NEW: u32 = 0x00;
+    pub const PASSWORD_PROMPT: u32 = 0x01;
+    pub const PASSWORD_OK: u32 = 0x02;
+
+    pub fn new(window_type: u32) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(EX_PACKET_ID)?;
+        buffer.write_u16(Self::EX_ID)?;
+        buffer.write_u32(window_type)?;
+        buffer.write_u32(0)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for Ex2ndPasswordCheck {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+/// Result of the PIN check, `wrong_attempts` is shown to the player.
+#[derive(Debug, Clone)]
+pub struct Ex2ndPasswordVerify {
+    buffer: SendablePacketBuffer,
+}
+
+impl Ex2ndPasswordVerify {
+    const EX_ID: u16 = 0x106;
+    pub const PASSWORD_OK: u32 = 0x00;
+    pub const PASSWORD_WRONG: u32 = 0x01;
+    pub const PASSWORD_BAN: u32 = 0x02;
+
+    pub fn new(mode: u32, wrong_attempts: u32) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(EX_PACKET_ID)?;
+        buffer.write_u16(Self::EX_ID)?;
+        buffer.write_u32(mode)?;
+        buffer.write_u32(wrong_attempts)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for Ex2ndPasswordVerify {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+/// Answer to creating or changing the PIN.
+#[derive(Debug, Clone)]
+pub struct Ex2ndPasswordAck {
+    buffer: SendablePacketBuffer,
+}
+
+impl Ex2ndPasswordAck {
+    const EX_ID: u16 = 0x107;
+    pub const SUCCESS: u32 = 0x00;
+    pub const WRONG_PATTERN: u32 = 0x01;
+
+    pub fn new(response: u32) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(EX_PACKET_ID)?;
+        buffer.write_u16(Self::EX_ID)?;
+        buffer.write(0)?;
+        buffer.write_u32(response)?;
+        buffer.write_u32(0)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for Ex2ndPasswordAck {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ex_2nd_password_verify() {
+        let mut packet = Ex2ndPasswordVerify::new(Ex2ndPasswordVerify::PASSWORD_WRONG, 3).unwrap();
+        assert_eq!(
+            packet.get_buffer_mut().get_data_mut(),
+            [13, 0, 0xFE, 0x06, 0x01, 1, 0, 0, 0, 3, 0, 0, 0]
+        );
+    }
+}
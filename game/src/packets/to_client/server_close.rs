@@ -0,0 +1,24 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Tells the client that the server closed the connection.
+#[derive(Debug, Clone)]
+pub struct ServerClose {
+    buffer: SendablePacketBuffer,
+}
+
+impl ServerClose {
+    const PACKET_ID: u8 = 0x20;
+
+    pub fn new() -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for ServerClose {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,26 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Skills of the character, it is empty until skills are stored.
+#[derive(Debug, Clone)]
+pub struct SkillList {
+    buffer: SendablePacketBuffer,
+}
+
+impl SkillList {
+    const PACKET_ID: u8 = 0x5F;
+
+    pub fn new() -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i32(0)?; // skills count
+        buffer.write_i32(0)?; // last learned skill id
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for SkillList {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
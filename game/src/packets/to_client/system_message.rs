@@ -0,0 +1,33 @@
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Only plain text messages are supported for now, they are sent as `$s1` with a single
+/// text parameter.
+#[derive(Debug, Clone)]
+pub struct SystemMessage {
+    buffer: SendablePacketBuffer,
+}
+
+impl SystemMessage {
+    const PACKET_ID: u8 = 0x62;
+    /// `$s1` system message id
+    const S1: u16 = 1983;
+    const TYPE_TEXT: u8 = 0;
+
+    pub fn text(message: &str) -> anyhow::Result<Self> {
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write(0)?; // unknown
+        buffer.write_u16(Self::S1)?;
+        buffer.write(1)?; // params count
+        buffer.write(Self::TYPE_TEXT)?;
+        buffer.write_string(Some(message))?;
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for SystemMessage {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,278 @@
+use l2_core::game_objects::paper_doll::PaperDoll;
+use l2_core::game_objects::player::Player;
+use l2_core::packets::common::SendablePacket;
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Everything the client shows about its own character. The data is split into blocks,
+/// each block starts with its size and the mask tells which of them are present.
+#[derive(Debug, Clone)]
+pub struct UserInfo {
+    buffer: SendablePacketBuffer,
+}
+
+impl UserInfo {
+    const PACKET_ID: u8 = 0x32;
+    const BLOCKS: u16 = 23;
+    /// All the blocks are sent, one bit per block.
+    const MASK: [u8; 3] = [0xFF, 0xFF, 0xFE];
+    // todo: stats, speed and collision come from the class and race data which
+    //  is not loaded yet, these are the values of a level 1 human fighter
+    const ATK_SPEED: i32 = 300;
+    const CAST_SPEED: i32 = 333;
+    const RUN_SPEED: i16 = 120;
+    const WALK_SPEED: i16 = 80;
+    const SWIM_SPEED: i16 = 50;
+    const COLLISION_RADIUS: f64 = 9.0;
+    const COLLISION_HEIGHT: f64 = 23.0;
+    const INVENTORY_LIMIT: i16 = 80;
+    const DEFAULT_NAME_COLOR: i32 = 0xFF_FF_FF;
+    const DEFAULT_TITLE_COLOR: i32 = 0xEC_F9_A2;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(player: &Player) -> anyhow::Result<Self> {
+        let char_model = &player.char_model;
+        let stats = &player.template.stats;
+        let paper_doll = player.paper_doll();
+        let enchant_level = |slot: PaperDoll| {
+            paper_doll[slot.slot()].map_or(0, |item| item.enchant_level.clamp(0, 127) as u8)
+        };
+        let mut blocks = Vec::new();
+
+        let mut basic_info = SendablePacketBuffer::empty();
+        basic_info.write_sized_string(Some(&char_model.name))?;
+        basic_info.write_bool(player.is_gm())?;
+        basic_info.write_i8(char_model.race_id)?;
+        basic_info.write_bool(char_model.sex != 0)?;
+        basic_info.write_i32(i32::from(char_model.base_class_id))?;
+        basic_info.write_i32(i32::from(player.get_class_id()))?;
+        basic_info.write(u8::try_from(char_model.level).unwrap_or(u8::MAX))?;
+        blocks.push(basic_info);
+
+        let mut base_stats = SendablePacketBuffer::empty();
+        for stat in [
+            stats.str, stats.dex, stats.con, stats.int, stats.wit, stats.men,
+        ] {
+            base_stats.write_i16(i16::try_from(stat).unwrap_or(i16::MAX))?;
+        }
+        base_stats.write_i16(0)?; // LUC
+        base_stats.write_i16(0)?; // CHA
+        blocks.push(base_stats);
+
+        let mut max_points = SendablePacketBuffer::empty();
+        max_points.write_i32(char_model.max_hp.round() as i32)?;
+        max_points.write_i32(char_model.max_mp.round() as i32)?;
+        max_points.write_i32(char_model.max_cp.round() as i32)?;
+        blocks.push(max_points);
+
+        let mut current_points = SendablePacketBuffer::empty();
+        current_points.write_i32(char_model.cur_hp.round() as i32)?;
+        current_points.write_i32(char_model.cur_mp.round() as i32)?;
+        current_points.write_i32(char_model.cur_cp.round() as i32)?;
+        current_points.write_i64(char_model.sp)?;
+        current_points.write_i64(char_model.exp)?;
+        current_points.write_f64(0.0)?; // exp percent of the current level, needs the exp table
+        blocks.push(current_points);
+
+        let mut enchant = SendablePacketBuffer::empty();
+        enchant.write(enchant_level(PaperDoll::RHand))?;
+        enchant.write(enchant_level(PaperDoll::Chest))?;
+        blocks.push(enchant);
+
+        let mut appearance = SendablePacketBuffer::empty();
+        appearance.write_i32(i32::from(char_model.hair_style.unwrap_or_default()))?;
+        appearance.write_i32(i32::from(char_model.hair_color.unwrap_or_default()))?;
+        appearance.write_i32(i32::from(char_model.face.unwrap_or_default()))?;
+        appearance.write_bool(true)?; // show hair accessory
+        blocks.push(appearance);
+
+        let mut status = SendablePacketBuffer::empty();
+        status.write(0)?; // mount type
+        status.write(0)?; // private store type
+        status.write_bool(char_model.can_craft.is_some_and(|c| c > 0))?;
+        status.write(0)?; // ability points used
+        blocks.push(status);
+
+        let mut combat_stats = SendablePacketBuffer::empty();
+        combat_stats.write_i16(if paper_doll[PaperDoll::RHand.slot()].is_some() {
+            40
+        } else {
+            20
+        })?;
+        combat_stats.write_i32(0)?; // p. atk
+        combat_stats.write_i32(Self::ATK_SPEED)?;
+        combat_stats.write_i32(0)?; // p. def
+        combat_stats.write_i32(0)?; // evasion
+        combat_stats.write_i32(0)?; // accuracy
+        combat_stats.write_i32(0)?; // critical
+        combat_stats.write_i32(0)?; // m. atk
+        combat_stats.write_i32(Self::CAST_SPEED)?;
+        combat_stats.write_i32(Self::ATK_SPEED)?;
+        combat_stats.write_i32(0)?; // magic evasion
+        combat_stats.write_i32(0)?; // m. def
+        combat_stats.write_i32(0)?; // magic accuracy
+        combat_stats.write_i32(0)?; // magic critical
+        blocks.push(combat_stats);
+
+        let mut elementals = SendablePacketBuffer::empty();
+        for _ in 0..6 {
+            elementals.write_i16(0)?; // fire, water, wind, earth, holy, dark defence
+        }
+        blocks.push(elementals);
+
+        let mut position = SendablePacketBuffer::empty();
+        position.write_i32(char_model.x)?;
+        position.write_i32(char_model.y)?;
+        position.write_i32(char_model.z)?;
+        position.write_i32(0)?; // vehicle id
+        blocks.push(position);
+
+        let mut speed = SendablePacketBuffer::empty();
+        for value in [
+            Self::RUN_SPEED,
+            Self::WALK_SPEED,
+            Self::SWIM_SPEED,
+            Self::SWIM_SPEED,
+            0, // flying mount
+            0,
+            0, // flying
+            0,
+        ] {
+            speed.write_i16(value)?;
+        }
+        blocks.push(speed);
+
+        let mut multiplier = SendablePacketBuffer::empty();
+        multiplier.write_f64(1.0)?; // movement
+        multiplier.write_f64(1.0)?; // attack speed
+        blocks.push(multiplier);
+
+        let mut collision = SendablePacketBuffer::empty();
+        collision.write_f64(Self::COLLISION_RADIUS)?;
+        collision.write_f64(Self::COLLISION_HEIGHT)?;
+        blocks.push(collision);
+
+        let mut atk_elemental = SendablePacketBuffer::empty();
+        atk_elemental.write_i8(-2)?; // no attack attribute
+        atk_elemental.write_i16(0)?;
+        blocks.push(atk_elemental);
+
+        let mut clan = SendablePacketBuffer::empty();
+        clan.write_sized_string(Some(char_model.title.as_deref().unwrap_or_default()))?;
+        clan.write_i16(char_model.sub_pledge.unwrap_or_default())?;
+        clan.write_i32(0)?; // clan id
+        clan.write_i32(0)?; // large crest id
+        clan.write_i32(0)?; // crest id
+        clan.write_i32(char_model.clan_privs.unwrap_or_default())?;
+        clan.write_bool(false)?; // is clan leader
+        clan.write_i32(0)?; // ally id
+        clan.write_i32(0)?; // ally crest id
+        clan.write_bool(false)?; // in matching room
+        blocks.push(clan);
+
+        let mut social = SendablePacketBuffer::empty();
+        social.write(0)?; // pvp flag
+        social.write_i32(char_model.reputation.unwrap_or_default())?;
+        social.write_bool(char_model.nobless.is_some_and(|n| n > 0))?;
+        social.write(0)?; // hero
+        social.write_i8(char_model.power_grade.unwrap_or_default())?;
+        social.write_i32(i32::from(char_model.pk_kills.unwrap_or_default()))?;
+        social.write_i32(i32::from(char_model.pvp_kills))?;
+        social.write_i16(0)?; // recommendations left
+        social.write_i16(0)?; // recommendations received
+        blocks.push(social);
+
+        let mut vitality = SendablePacketBuffer::empty();
+        vitality.write_i32(char_model.vitality_points)?;
+        vitality.write(0)?; // vitality bonus
+        vitality.write_i32(char_model.fame)?;
+        vitality.write_i32(char_model.rb_points)?;
+        blocks.push(vitality);
+
+        let mut slots = SendablePacketBuffer::empty();
+        slots.write(0)?; // talisman slots
+        slots.write(0)?; // brooch jewel slots
+        slots.write(0)?; // team
+        slots.write_bytes(&[0; 4])?;
+        blocks.push(slots);
+
+        let mut movements = SendablePacketBuffer::empty();
+        movements.write(0)?; // 1 in water, 2 flying
+        movements.write_bool(true)?; // running
+        blocks.push(movements);
+
+        let mut colors = SendablePacketBuffer::empty();
+        colors.write_i32(Self::DEFAULT_NAME_COLOR)?;
+        colors.write_i32(char_model.title_color.unwrap_or(Self::DEFAULT_TITLE_COLOR))?;
+        blocks.push(colors);
+
+        let mut inventory = SendablePacketBuffer::empty();
+        inventory.write_i16(0)?;
+        inventory.write_i16(0)?;
+        inventory.write_i16(Self::INVENTORY_LIMIT)?;
+        inventory.write(0)?; // cursed weapon level
+        blocks.push(inventory);
+
+        let mut true_hero = SendablePacketBuffer::empty();
+        true_hero.write_i32(0)?;
+        true_hero.write_i16(0)?;
+        true_hero.write(0)?;
+        blocks.push(true_hero);
+
+        // every block is prefixed with its size, the relation block is the only exception
+        let blocks: Vec<Vec<u8>> = blocks
+            .iter_mut()
+            .map(SendablePacketBuffer::get_data)
+            .collect();
+        let relation = 0i32;
+        let size = 2 + Self::MASK.len() + 4 + blocks.iter().map(Vec::len).sum::<usize>();
+
+        let mut buffer = SendablePacketBuffer::new();
+        buffer.write(Self::PACKET_ID)?;
+        buffer.write_i32(player.get_object_id())?;
+        buffer.write_i32(i32::try_from(size)?)?;
+        buffer.write_u16(Self::BLOCKS)?;
+        buffer.write_bytes(&Self::MASK)?;
+        buffer.write_i32(relation)?;
+        for block in &blocks {
+            buffer.write_bytes(block)?;
+        }
+        Ok(Self { buffer })
+    }
+}
+
+impl SendablePacket for UserInfo {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_player;
+
+    #[test]
+    fn test_user_info_blocks() {
+        let mut packet = UserInfo::new(&test_player()).unwrap();
+        let data = packet.get_buffer_mut().get_data();
+        // size, opcode, object id
+        assert_eq!(data[2], UserInfo::PACKET_ID);
+        assert_eq!(data[3..7], 7i32.to_le_bytes());
+        let size = usize::try_from(i32::from_le_bytes(data[7..11].try_into().unwrap())).unwrap();
+        // the size covers everything from the block count till the end
+        assert_eq!(size, data.len() - 11);
+        assert_eq!(data[11..16], [23, 0, 0xFF, 0xFF, 0xFE]);
+        // skip the relation and walk through the blocks, there must be exactly 22 of them
+        let mut pos = 20;
+        let mut count = 0;
+        while pos < data.len() {
+            pos += usize::from(u16::from_le_bytes([data[pos], data[pos + 1]]));
+            count += 1;
+        }
+        assert_eq!(pos, data.len());
+        assert_eq!(count, 22);
+        // basic info: name size and name
+        assert_eq!(data[22..24], [3, 0]);
+        assert_eq!(data[24..30], [b'B', 0, b'o', 0, b'b', 0]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::client_thread::ClientHandler;
+use crate::controller::Controller;
+use entities::entities::sea_orm_active_enums::ItemLocation;
+use entities::entities::{character, item};
+use l2_core::game_objects::char_template::CharTemplates;
+use l2_core::game_objects::paper_doll::PaperDoll;
+use l2_core::game_objects::player::Player;
+use l2_core::tests::DBPool;
+use l2_core::traits::handlers::PacketHandler;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+/// Two ends of a local TCP connection, the first one is meant for the handler under test.
+pub async fn connected_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (peer, _) = listener.accept().await.unwrap();
+    (stream, peer)
+}
+
+/// Reads the next packet sent to an unencrypted client, the size prefix is stripped.
+pub async fn read_packet(client: &mut TcpStream) -> Vec<u8> {
+    let mut size = [0; 2];
+    client.read_exact(&mut size).await.unwrap();
+    let mut body = vec![0; usize::from(u16::from_le_bytes(size)) - 2];
+    client.read_exact(&mut body).await.unwrap();
+    body
+}
+
+/// A client handler and the socket of its client, the socket must be kept while the handler sends.
+pub async fn test_client(
+    db_pool: DBPool,
+    controller: Arc<Controller>,
+) -> (ClientHandler, TcpStream) {
+    let (stream, client) = connected_pair().await;
+    (ClientHandler::new(stream, db_pool, controller), client)
+}
+
+/// Level 2 human fighter Bob (id 7) of user 1.
+pub fn test_char() -> character::Model {
+    character::Model {
+        id: 7,
+        name: "Bob".to_string(),
+        level: 2,
+        delete_at: None,
+        user_id: 1,
+        max_hp: 126.0,
+        cur_hp: 100.5,
+        max_cp: 0.0,
+        cur_cp: 0.0,
+        cur_mp: 50.0,
+        max_mp: 38.0,
+        face: Some(0),
+        hair_style: Some(1),
+        hair_color: Some(2),
+        sex: 1,
+        heading: None,
+        x: -71338,
+        y: 258_271,
+        z: -3104,
+        exp: 68,
+        exp_before_death: None,
+        sp: 10,
+        reputation: None,
+        fame: 0,
+        rb_points: 0,
+        pvp_kills: 0,
+        pk_kills: None,
+        race_id: 0,
+        class_id: None,
+        base_class_id: 0,
+        transform_id: 0,
+        can_craft: None,
+        title: None,
+        title_color: None,
+        access_level: Some(0),
+        online: None,
+        online_time: None,
+        char_slot: None,
+        last_access: None,
+        clan_privs: None,
+        wants_peace: None,
+        power_grade: None,
+        nobless: Some(0),
+        sub_pledge: None,
+        lvl_joined_academy: 0,
+        apprentice: 0,
+        sponsor: 0,
+        clan_join_expiry_time: None,
+        clan_create_expiry_time: None,
+        bookmark_slot: 0,
+        vitality_points: 140_000,
+        created_at: None,
+        language: None,
+        faction: 0,
+        pc_cafe_points: 0,
+    }
+}
+
+/// Level 2 human fighter Bob (id 7) with an equipped +3 sword and 10 potions in the inventory.
+pub fn test_player() -> Player {
+    let templates = CharTemplates::load("../config/char_templates.yaml");
+    let char_model = test_char();
+    let items = vec![
+        item::Model {
+            id: 1,
+            owner: 7,
+            item_id: 2369,
+            count: 1,
+            enchant_level: 3,
+            loc: ItemLocation::Paperdoll,
+            loc_data: i32::from(PaperDoll::RHand as u8),
+        },
+        item::Model {
+            id: 2,
+            owner: 7,
+            item_id: 1060,
+            count: 10,
+            enchant_level: 0,
+            loc: ItemLocation::Inventory,
+            loc_data: 0,
+        },
+    ];
+    Player::new(char_model, templates.get(0).unwrap().clone(), items)
+}
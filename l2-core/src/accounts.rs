@@ -0,0 +1,343 @@
+//! Account backends for the login server, selected by `accounts` in `login.yaml`.
+//!
+//! - `local`: argon2 password hashes in the `user` table (default);
+//! - `http`: a JSON API, every account still gets a row in the `user` table, but its
+//!   password is not stored there.
+//!
+//! The HTTP backend sends `POST <url>/auth` and `POST <url>/register` with
+//! `{"username": "...", "password": "..."}` and expects `{"status": "..."}` back, where the
+//! status is one of `ok`, `wrong_password` or `not_found`. `POST <url>/change_password`
+//! gets `new_password` in addition and answers the same way.
+use crate::config::login::AccountProviderConfig;
+use crate::hash_password;
+use crate::traits::accounts::{AccountProvider, Credentials};
+use anyhow::bail;
+use async_trait::async_trait;
+use entities::entities::user;
+use entities::DBPool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[must_use]
+pub fn new_account_provider(cfg: &AccountProviderConfig) -> Arc<dyn AccountProvider> {
+    match cfg {
+        AccountProviderConfig::Local => Arc::new(LocalAccountProvider),
+        AccountProviderConfig::Http { url, timeout } => {
+            Arc::new(HttpAccountProvider::new(url, Duration::from_secs(*timeout)))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalAccountProvider;
+
+#[async_trait]
+impl AccountProvider for LocalAccountProvider {
+    async fn check_password(
+        &self,
+        account: Option<&user::Model>,
+        _username: &str,
+        password: &str,
+    ) -> anyhow::Result<Credentials> {
+        Ok(match account {
+            None => Credentials::UnknownAccount,
+            Some(user) if user.verify_password(password).await => Credentials::Valid,
+            Some(_) => Credentials::WrongPassword,
+        })
+    }
+
+    async fn register(
+        &self,
+        db_pool: &DBPool,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<user::Model> {
+        let password_hash = hash_password(password).await?;
+        user::Model::register(db_pool, username, password_hash).await
+    }
+
+    async fn change_password(
+        &self,
+        db_pool: &DBPool,
+        account: user::Model,
+        current_password: &str,
+        new_password: &str,
+    ) -> anyhow::Result<Credentials> {
+        if !account.verify_password(current_password).await {
+            return Ok(Credentials::WrongPassword);
+        }
+        let password_hash = hash_password(new_password).await?;
+        account.set_password_hash(db_pool, password_hash).await?;
+        Ok(Credentials::Valid)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HttpCredentials<'a> {
+    username: &'a str,
+    password: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_password: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum HttpStatus {
+    Ok,
+    WrongPassword,
+    NotFound,
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpResponse {
+    status: HttpStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpAccountProvider {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpAccountProvider {
+    ///
+    /// # Panics
+    /// - when TLS backend can't be initialized
+    #[must_use]
+    pub fn new(url: &str, timeout: Duration) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("Can not create HTTP client"),
+        }
+    }
+
+    async fn post(
+        &self,
+        path: &str,
+        credentials: &HttpCredentials<'_>,
+    ) -> anyhow::Result<HttpStatus> {
+        let response: HttpResponse = self
+            .client
+            .post(format!("{}/{path}", self.url))
+            .json(credentials)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.status)
+    }
+}
+
+#[async_trait]
+impl AccountProvider for HttpAccountProvider {
+    async fn check_password(
+        &self,
+        _account: Option<&user::Model>,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<Credentials> {
+        let credentials = HttpCredentials {
+            username,
+            password,
+            new_password: None,
+        };
+        Ok(match self.post("auth", &credentials).await? {
+            HttpStatus::Ok => Credentials::Valid,
+            HttpStatus::WrongPassword => Credentials::WrongPassword,
+            HttpStatus::NotFound => Credentials::UnknownAccount,
+        })
+    }
+
+    async fn register(
+        &self,
+        db_pool: &DBPool,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<user::Model> {
+        let credentials = HttpCredentials {
+            username,
+            password,
+            new_password: None,
+        };
+        let status = self.post("register", &credentials).await?;
+        if status != HttpStatus::Ok {
+            bail!("Account {username} was not created by the backend: {status:?}");
+        }
+        // the password lives in the backend, an empty hash never matches locally
+        user::Model::register(db_pool, username, String::new()).await
+    }
+
+    async fn change_password(
+        &self,
+        _db_pool: &DBPool,
+        account: user::Model,
+        current_password: &str,
+        new_password: &str,
+    ) -> anyhow::Result<Credentials> {
+        let credentials = HttpCredentials {
+            username: &account.username,
+            password: current_password,
+            new_password: Some(new_password),
+        };
+        Ok(match self.post("change_password", &credentials).await? {
+            HttpStatus::Ok => Credentials::Valid,
+            HttpStatus::WrongPassword => Credentials::WrongPassword,
+            HttpStatus::NotFound => Credentials::UnknownAccount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::setup_test_db;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Answers each request with the status picked by `answer` from the posted body.
+    async fn stub_server<F>(answer: F) -> (String, JoinHandle<()>)
+    where
+        F: Fn(&str, &serde_json::Value) -> &'static str + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/l2", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut data = Vec::new();
+                let mut buf = [0u8; 1024];
+                let (head, body) = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    data.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&data).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            break (head.to_string(), body.to_string());
+                        }
+                    }
+                };
+                let path = head.split_whitespace().nth(1).unwrap().to_string();
+                let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let status = answer(&path, &json);
+                let response_body = format!("{{\"status\":\"{status}\"}}");
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response_body}",
+                    response_body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, handle)
+    }
+
+    fn site(path: &str, body: &serde_json::Value) -> &'static str {
+        match (path, body["username"].as_str(), body["password"].as_str()) {
+            ("/l2/auth", Some("admin"), Some("secret")) => "ok",
+            ("/l2/auth", Some("admin"), _) => "wrong_password",
+            ("/l2/register", Some(_), Some(_)) => "ok",
+            ("/l2/change_password", Some("admin"), Some("secret"))
+                if body["new_password"].as_str().is_some() =>
+            {
+                "ok"
+            }
+            ("/l2/change_password", Some("admin"), _) => "wrong_password",
+            _ => "not_found",
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_check_password() {
+        let (url, server) = stub_server(site).await;
+        let provider = HttpAccountProvider::new(&url, Duration::from_secs(5));
+        let check = |password: &'static str, username: &'static str| {
+            let provider = provider.clone();
+            async move {
+                provider
+                    .check_password(None, username, password)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(check("secret", "admin").await, Credentials::Valid);
+        assert_eq!(check("wrong", "admin").await, Credentials::WrongPassword);
+        assert_eq!(check("secret", "nobody").await, Credentials::UnknownAccount);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_http_register_creates_local_account() {
+        let (url, server) = stub_server(site).await;
+        let db_pool = setup_test_db().await;
+        let provider = HttpAccountProvider::new(&url, Duration::from_secs(5));
+        let user = provider
+            .register(&db_pool, "newbie", "secret")
+            .await
+            .unwrap();
+        assert_eq!(user.username, "newbie");
+        // the password is not known locally
+        assert!(!user.verify_password("secret").await);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_http_change_password() {
+        let (url, server) = stub_server(site).await;
+        let db_pool = setup_test_db().await;
+        let provider = HttpAccountProvider::new(&url, Duration::from_secs(5));
+        let user = user::Model::register(&db_pool, "admin", String::new())
+            .await
+            .unwrap();
+        let changed = provider
+            .change_password(&db_pool, user.clone(), "secret", "new secret")
+            .await;
+        assert_eq!(changed.unwrap(), Credentials::Valid);
+        let changed = provider
+            .change_password(&db_pool, user, "wrong", "new secret")
+            .await;
+        assert_eq!(changed.unwrap(), Credentials::WrongPassword);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_local_provider() {
+        let db_pool = setup_test_db().await;
+        let provider = LocalAccountProvider;
+        let user = provider
+            .register(&db_pool, "local", "secret")
+            .await
+            .unwrap();
+        let check = provider
+            .check_password(Some(&user), "local", "secret")
+            .await;
+        assert_eq!(check.unwrap(), Credentials::Valid);
+        let check = provider.check_password(Some(&user), "local", "wrong").await;
+        assert_eq!(check.unwrap(), Credentials::WrongPassword);
+        let check = provider.check_password(None, "other", "secret").await;
+        assert_eq!(check.unwrap(), Credentials::UnknownAccount);
+        let changed = provider
+            .change_password(&db_pool, user.clone(), "wrong", "changed")
+            .await;
+        assert_eq!(changed.unwrap(), Credentials::WrongPassword);
+        let changed = provider
+            .change_password(&db_pool, user, "secret", "changed")
+            .await;
+        assert_eq!(changed.unwrap(), Credentials::Valid);
+        let user = user::Model::find_by_username(&db_pool, "local")
+            .await
+            .unwrap();
+        assert!(user.verify_password("changed").await);
+    }
+}
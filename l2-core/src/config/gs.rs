@@ -1,5 +1,7 @@
 use crate::config::login::GSMessages;
-use crate::dto::{Database, InboundConnection, OutboundConnection, Runtime, ServerHost};
+use crate::dto::{
+    Database, InboundConnection, IpBanConfig, OutboundConnection, Runtime, ServerHost,
+};
 use crate::packets::common::ServerType;
 use crate::traits::ServerConfig;
 use log::{error, info};
@@ -40,12 +42,25 @@ pub struct GSServer {
     pub ip_config: Vec<ServerHost>,
     #[serde(default = "default_chars_on_acc")]
     pub max_chars_on_account: u8,
+    #[serde(default)]
+    pub ip_ban: IpBanConfig,
+    #[serde(default)]
+    pub secondary_auth: SecondaryAuth,
+    /// Starting stats, position and items of new characters.
+    #[serde(default = "default_char_templates")]
+    pub char_templates: String,
+    #[serde(default)]
+    pub char_deletion: CharDeletion,
 }
 
 fn default_chars_on_acc() -> u8 {
     5
 }
 
+fn default_char_templates() -> String {
+    "config/char_templates.yaml".to_string()
+}
+
 fn deserialize_hex_to_bigint<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
 where
     D: Deserializer<'de>,
@@ -157,3 +172,43 @@ pub struct Listeners {
 pub struct Client {
     pub timeout: u8,
 }
+
+/// Secondary password (PIN) asked before the character selection, durations are in seconds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SecondaryAuth {
+    /// Accounts without a PIN have to create one, otherwise only accounts having a PIN are asked.
+    pub required: bool,
+    /// The account gets locked after that many wrong PINs, 0 disables the check.
+    pub max_attempts: u32,
+    pub lock_duration: i64,
+}
+
+/// Deleted characters are kept for a while and can be restored from the character selection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CharDeletion {
+    /// 0 deletes characters immediately.
+    pub delay_days: u32,
+    /// How often (in seconds) expired characters are purged from the DB.
+    pub purge_interval: u64,
+}
+
+impl Default for CharDeletion {
+    fn default() -> Self {
+        Self {
+            delay_days: 7,
+            purge_interval: 3600,
+        }
+    }
+}
+
+impl Default for SecondaryAuth {
+    fn default() -> Self {
+        Self {
+            required: false,
+            max_attempts: 5,
+            lock_duration: 1800,
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use crate::dto::{Database, InboundConnection, Runtime};
+use crate::dto::{Database, InboundConnection, IpBanConfig, Runtime};
 use crate::traits::ServerConfig;
 use num::BigInt;
 use num_traits::Num;
 use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
+use std::net::IpAddr;
 use tracing::info;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -18,6 +20,21 @@ pub struct LoginServer {
     pub listeners: Listeners,
     pub database: Database,
     pub client: Client,
+    #[serde(default)]
+    pub ip_ban: IpBanConfig,
+    #[serde(default)]
+    pub brute_force: BruteForceProtection,
+    #[serde(default)]
+    pub dual_box: DualBoxProtection,
+    #[serde(default)]
+    pub accounts: AccountProviderConfig,
+    /// Accounts with more characters on a game server are not let in, the client can't show them.
+    #[serde(default = "default_max_chars_on_account")]
+    pub max_chars_on_account: u8,
+}
+
+fn default_max_chars_on_account() -> u8 {
+    7
 }
 
 impl ServerConfig for LoginServer {
@@ -93,6 +110,81 @@ pub struct Client {
     pub timeout: u8,
     pub show_licence: bool,
     pub enable_cmdline_login: bool,
+    pub show_pi_agreement: bool,
+}
+
+/// Thresholds for failed logins, all durations are in seconds, 0 disables the check.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BruteForceProtection {
+    /// Failures older than this are forgotten.
+    pub failure_window: i64,
+    pub max_ip_failures: u32,
+    pub ip_ban_duration: i64,
+    pub max_account_failures: u32,
+    pub account_lock_duration: i64,
+    /// Most IPs (and accounts) with failed logins remembered at once, the oldest unlocked
+    /// ones are forgotten to make room for new ones, 0 means no limit.
+    pub max_tracked: usize,
+    /// How often expired failures and locks are forgotten.
+    pub cleanup_interval: u64,
+}
+
+impl Default for BruteForceProtection {
+    fn default() -> Self {
+        Self {
+            failure_window: 300,
+            max_ip_failures: 10,
+            ip_ban_duration: 600,
+            max_account_failures: 5,
+            account_lock_duration: 900,
+            max_tracked: 100_000,
+            cleanup_interval: 300,
+        }
+    }
+}
+
+/// Where account passwords are checked, see `l2_core::accounts`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum AccountProviderConfig {
+    /// Argon2 hashes in the local `user` table.
+    #[default]
+    Local,
+    /// External JSON API, e.g. the community site.
+    Http {
+        url: String,
+        /// Request timeout in seconds.
+        #[serde(default = "default_http_timeout")]
+        timeout: u64,
+    },
+}
+
+fn default_http_timeout() -> u64 {
+    5
+}
+
+/// Limit of accounts logged in from the same client IP at the same time.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DualBoxProtection {
+    /// 0 disables the check.
+    pub max_accounts_per_ip: u32,
+    /// Custom limits for some IPs (e.g. internet cafes), 0 means unlimited.
+    pub exemptions: HashMap<IpAddr, u32>,
+}
+
+impl DualBoxProtection {
+    /// Returns the limit for the IP, `None` if there is no limit.
+    #[must_use]
+    pub fn limit_for(&self, ip: &IpAddr) -> Option<u32> {
+        let limit = self
+            .exemptions
+            .get(ip)
+            .copied()
+            .unwrap_or(self.max_accounts_per_ip);
+        (limit > 0).then_some(limit)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -83,7 +83,7 @@ impl Encryption {
     pub fn decrypt(&self, raw: &mut [u8]) -> Result<(), Packet> {
         let size = raw.len();
         let offset = 0;
-        if size % 8 > 0 || offset + size > raw.len() {
+        if !size.is_multiple_of(8) || offset + size > raw.len() {
             return Err(Packet::DecryptBlowfishError);
         }
         for chunk in raw.chunks_mut(8) {
@@ -100,7 +100,6 @@ impl Encryption {
 
 #[cfg(test)]
 mod test {
-    use super::*;
     use crate::crypt::login::Encryption;
 
     #[test]
@@ -118,7 +117,6 @@ mod test {
             128, 157, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
         let decryptor = Encryption::from_u8_key(&key);
-        let data_len = data.len();
         let res = decryptor.decrypt(&mut data);
         assert!(res.is_ok(), "Result must be ok");
         assert_eq!(
@@ -142,7 +140,6 @@ mod test {
             12, 84, 204, 79, 78, 136, 249, 67, 63, 70, 44, 61, 28, 224, 9, 31,
         ];
         let decryptor = Encryption::from_u8_key(&key);
-        let data_len = data.len();
         let res = decryptor.decrypt(&mut data);
         assert!(res.is_ok(), "Result must be ok");
         assert_eq!(
@@ -241,7 +238,6 @@ mod test {
             174, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
         let decryptor = Encryption::from_u8_key(&key);
-        let data_len = data.len();
         let res = decryptor.decrypt(&mut data);
         assert!(res.is_ok(), "Result must be ok");
         assert_eq!(
@@ -30,6 +30,28 @@ fn default_timeout() -> u64 {
 fn default_max_lifetime() -> u64 {
     60 * 60
 }
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpBanConfig {
+    /// Optional file with extra bans, it is re-read when modified.
+    pub file: Option<String>,
+    /// How often (in seconds) to check the file for changes.
+    #[serde(default = "default_ban_reload_interval")]
+    pub reload_interval: u64,
+}
+
+impl Default for IpBanConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            reload_interval: default_ban_reload_interval(),
+        }
+    }
+}
+
+fn default_ban_reload_interval() -> u64 {
+    60
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Runtime {
     pub worker_threads: usize,
@@ -0,0 +1,96 @@
+use crate::game_objects::paper_doll::PaperDoll;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BaseStats {
+    pub str: i32,
+    pub dex: i32,
+    pub con: i32,
+    pub int: i32,
+    pub wit: i32,
+    pub men: i32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Location {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// An item given to a new character, it is equipped when `slot` is set.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StartItem {
+    pub item_id: i32,
+    #[serde(default = "default_count")]
+    pub count: i64,
+    pub slot: Option<PaperDoll>,
+}
+
+fn default_count() -> i64 {
+    1
+}
+
+/// Starting stats, position and items of a class which can be chosen on character creation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharTemplate {
+    pub class_id: i8,
+    pub race_id: i8,
+    pub stats: BaseStats,
+    pub hp: f64,
+    pub mp: f64,
+    pub cp: f64,
+    pub spawn: Location,
+    #[serde(default)]
+    pub items: Vec<StartItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct CharTemplates {
+    templates: Vec<CharTemplate>,
+}
+
+impl CharTemplates {
+    ///
+    /// # Panics
+    /// - when the file can't be opened or parsed
+    #[must_use]
+    pub fn load(file_name: &str) -> Self {
+        let file = File::open(file_name)
+            .unwrap_or_else(|e| panic!("Failed to open char templates: {file_name}. Error: {e}"));
+        serde_yaml::from_reader(BufReader::new(file))
+            .unwrap_or_else(|e| panic!("Unable to parse {file_name}, the format is incorrect, {e}"))
+    }
+
+    /// All the templates in the order they are shown on the character creation screen.
+    #[must_use]
+    pub fn all(&self) -> &[CharTemplate] {
+        &self.templates
+    }
+
+    #[must_use]
+    pub fn get(&self, class_id: i8) -> Option<&CharTemplate> {
+        self.templates.iter().find(|t| t.class_id == class_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_templates() {
+        let templates = CharTemplates::load("../config/char_templates.yaml");
+        assert_eq!(templates.all().len(), 9);
+        let human_fighter = templates.get(0).unwrap();
+        assert_eq!(human_fighter.race_id, 0);
+        assert!(human_fighter
+            .items
+            .iter()
+            .any(|item| item.slot == Some(PaperDoll::Chest)));
+        assert!(templates.get(1).is_none());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod char_template;
+pub mod paper_doll;
+pub mod player;
@@ -0,0 +1,105 @@
+use serde::Deserialize;
+
+/// Equipment slots, the value is stored as `loc_data` of equipped items.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaperDoll {
+    Under = 0,
+    Head = 1,
+    Hair = 2,
+    Hair2 = 3,
+    Neck = 4,
+    RHand = 5,
+    Chest = 6,
+    LHand = 7,
+    REar = 8,
+    LEar = 9,
+    Gloves = 10,
+    Legs = 11,
+    Feet = 12,
+    RFinger = 13,
+    LFinger = 14,
+    LBracelet = 15,
+    RBracelet = 16,
+    Agathion1 = 17,
+    Agathion2 = 18,
+    Agathion3 = 19,
+    Agathion4 = 20,
+    Agathion5 = 21,
+    Deco1 = 22,
+    Deco2 = 23,
+    Deco3 = 24,
+    Deco4 = 25,
+    Deco5 = 26,
+    Deco6 = 27,
+    Cloak = 28,
+    Belt = 29,
+    Brooch = 30,
+    BroochJewel1 = 31,
+    BroochJewel2 = 32,
+    BroochJewel3 = 33,
+    BroochJewel4 = 34,
+    BroochJewel5 = 35,
+    BroochJewel6 = 36,
+}
+
+impl PaperDoll {
+    pub const TOTAL_SLOTS: usize = 37;
+
+    /// The order equipped items are sent in, e.g. in `CharSelectionInfo` (protocol 110),
+    /// the right hand is sent twice, the second time for two-handed weapons.
+    pub const ORDER: [PaperDoll; 33] = [
+        Self::Under,
+        Self::REar,
+        Self::LEar,
+        Self::Neck,
+        Self::RFinger,
+        Self::LFinger,
+        Self::Head,
+        Self::RHand,
+        Self::LHand,
+        Self::Gloves,
+        Self::Chest,
+        Self::Legs,
+        Self::Feet,
+        Self::Cloak,
+        Self::RHand,
+        Self::Hair,
+        Self::Hair2,
+        Self::RBracelet,
+        Self::LBracelet,
+        Self::Deco1,
+        Self::Deco2,
+        Self::Deco3,
+        Self::Deco4,
+        Self::Deco5,
+        Self::Deco6,
+        Self::Belt,
+        Self::Brooch,
+        Self::BroochJewel1,
+        Self::BroochJewel2,
+        Self::BroochJewel3,
+        Self::BroochJewel4,
+        Self::BroochJewel5,
+        Self::BroochJewel6,
+    ];
+
+    /// Slots which can have an appearance (visual id) different from the item.
+    pub const VISUAL_ORDER: [PaperDoll; 9] = [
+        Self::RHand,
+        Self::LHand,
+        Self::Gloves,
+        Self::Chest,
+        Self::Legs,
+        Self::Feet,
+        Self::RHand,
+        Self::Hair,
+        Self::Hair2,
+    ];
+
+    #[must_use]
+    pub fn slot(self) -> usize {
+        self as usize
+    }
+}
@@ -0,0 +1,125 @@
+use crate::game_objects::char_template::{CharTemplate, CharTemplates};
+use crate::game_objects::paper_doll::PaperDoll;
+use anyhow::anyhow;
+use entities::entities::sea_orm_active_enums::ItemLocation;
+use entities::entities::{character, item};
+use sea_orm::DatabaseConnection;
+
+/// A character which entered the world, it lives in memory while the client is in game.
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub char_model: character::Model,
+    pub template: CharTemplate,
+    pub items: Vec<item::Model>,
+}
+
+impl Player {
+    #[must_use]
+    pub fn new(
+        char_model: character::Model,
+        template: CharTemplate,
+        items: Vec<item::Model>,
+    ) -> Self {
+        Self {
+            char_model,
+            template,
+            items,
+        }
+    }
+
+    /// Loads the items of the character and picks the template of its base class.
+    ///
+    /// # Errors
+    /// - when there is no template for the base class of the character
+    /// - when DB is not accessible
+    pub async fn load(
+        db_pool: &DatabaseConnection,
+        char_model: character::Model,
+        templates: &CharTemplates,
+    ) -> anyhow::Result<Self> {
+        let template = templates
+            .get(char_model.base_class_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("No template for class {}", char_model.base_class_id))?;
+        let items = item::Model::find_inventory_items(db_pool, char_model.id).await?;
+        Ok(Self::new(char_model, template, items))
+    }
+
+    #[must_use]
+    pub fn get_object_id(&self) -> i32 {
+        self.char_model.id
+    }
+
+    #[must_use]
+    pub fn get_class_id(&self) -> i8 {
+        self.char_model
+            .class_id
+            .unwrap_or(self.char_model.base_class_id)
+    }
+
+    #[must_use]
+    pub fn is_gm(&self) -> bool {
+        self.char_model.access_level.is_some_and(|level| level > 0)
+    }
+
+    /// Equipped items indexed by [`PaperDoll::slot`].
+    #[must_use]
+    pub fn paper_doll(&self) -> [Option<&item::Model>; PaperDoll::TOTAL_SLOTS] {
+        let mut paper_doll = [None; PaperDoll::TOTAL_SLOTS];
+        for item in self
+            .items
+            .iter()
+            .filter(|item| item.loc == ItemLocation::Paperdoll)
+        {
+            if let Some(slot) = usize::try_from(item.loc_data)
+                .ok()
+                .and_then(|slot| paper_doll.get_mut(slot))
+            {
+                *slot = Some(item);
+            }
+        }
+        paper_doll
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{new_char, setup_test_db};
+    use entities::entities::user;
+    use sea_orm::ActiveValue;
+
+    #[tokio::test]
+    async fn test_load_player() {
+        let db_pool = setup_test_db().await;
+        let templates = CharTemplates::load("../config/char_templates.yaml");
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let sword = item::ActiveModel {
+            item_id: ActiveValue::Set(2369),
+            loc: ActiveValue::Set(ItemLocation::Paperdoll),
+            loc_data: ActiveValue::Set(i32::from(PaperDoll::RHand as u8)),
+            ..Default::default()
+        };
+        let potions = item::ActiveModel {
+            item_id: ActiveValue::Set(1060),
+            count: ActiveValue::Set(10),
+            loc: ActiveValue::Set(ItemLocation::Inventory),
+            ..Default::default()
+        };
+        let char_model =
+            character::Model::create(&db_pool, new_char("Bob", user.id), vec![sword, potions])
+                .await
+                .unwrap();
+        let player = Player::load(&db_pool, char_model, &templates)
+            .await
+            .unwrap();
+        assert_eq!(player.get_class_id(), 0);
+        assert_eq!(player.items.len(), 2);
+        let paper_doll = player.paper_doll();
+        assert_eq!(paper_doll[PaperDoll::RHand.slot()].unwrap().item_id, 2369);
+        assert!(paper_doll[PaperDoll::Chest.slot()].is_none());
+        assert!(!player.is_gm());
+    }
+}
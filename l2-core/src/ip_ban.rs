@@ -0,0 +1,329 @@
+//! IP ban list shared by login and game servers.
+//!
+//! Entries come from three sources:
+//! - the `ip_ban` table, loaded once on startup with [`IpBanList::load_from_db`];
+//! - bans issued at runtime (e.g. `RequestTempBan` from a game server);
+//! - an optional plain text file which is re-read whenever it changes on disk.
+//!
+//! The file has one entry per line: `<ip or cidr> [expires_at] [# reason]`, where
+//! `expires_at` is a unix timestamp in seconds, omitted or `0` means a permanent ban.
+//! Empty lines and lines starting with `#` are ignored.
+use crate::dto::IpBanConfig;
+use chrono::Utc;
+use dashmap::DashMap;
+use entities::entities::ip_ban;
+use entities::DBPool;
+use pnet::ipnetwork::IpNetwork;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpBanEntry {
+    pub network: IpNetwork,
+    /// Unix timestamp (seconds) of the ban expiration, `None` for permanent bans.
+    pub expires_at: Option<i64>,
+    pub reason: Option<String>,
+}
+
+impl IpBanEntry {
+    #[must_use]
+    pub fn permanent(network: IpNetwork, reason: Option<String>) -> Self {
+        Self {
+            network,
+            expires_at: None,
+            reason,
+        }
+    }
+    #[must_use]
+    pub fn timed(network: IpNetwork, expires_at: i64, reason: Option<String>) -> Self {
+        Self {
+            network,
+            expires_at: Some(expires_at),
+            reason,
+        }
+    }
+    #[must_use]
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+    #[must_use]
+    pub fn matches(&self, ip: IpAddr) -> bool {
+        self.network.contains(ip)
+    }
+}
+
+impl TryFrom<ip_ban::Model> for IpBanEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(model: ip_ban::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            network: IpNetwork::from_str(&model.network)?,
+            expires_at: model.expires_at,
+            reason: model.reason,
+        })
+    }
+}
+
+impl FromStr for IpBanEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (entry, reason) = match line.split_once('#') {
+            Some((entry, reason)) => (entry, Some(reason.trim().to_string())),
+            None => (line, None),
+        };
+        let reason = reason.filter(|r| !r.is_empty());
+        let mut parts = entry.split_whitespace();
+        let network = parts
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Missing network in ban entry: {line}"))?;
+        let network = IpNetwork::from_str(network)?;
+        let expires_at = match parts.next() {
+            Some(ts) => Some(ts.parse::<i64>()?).filter(|ts| *ts > 0),
+            None => None,
+        };
+        Ok(Self {
+            network,
+            expires_at,
+            reason,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct IpBanList {
+    /// Bans from DB and the ones issued at runtime, keyed by network.
+    entries: DashMap<IpNetwork, IpBanEntry>,
+    /// Bans from the file, the whole list is replaced on every reload.
+    file_entries: RwLock<Vec<IpBanEntry>>,
+    file: Option<PathBuf>,
+    file_modified: Mutex<Option<SystemTime>>,
+    reload_interval: Duration,
+}
+
+impl IpBanList {
+    #[must_use]
+    pub fn new(config: &IpBanConfig) -> Self {
+        Self {
+            file: config.file.as_ref().map(PathBuf::from),
+            reload_interval: Duration::from_secs(config.reload_interval),
+            ..Self::default()
+        }
+    }
+
+    pub fn ban(&self, entry: IpBanEntry) {
+        self.entries.insert(entry.network, entry);
+    }
+
+    pub fn unban(&self, network: &IpNetwork) -> Option<IpBanEntry> {
+        self.entries.remove(network).map(|(_, entry)| entry)
+    }
+
+    /// Returns the active ban covering the ip, expired runtime bans are dropped on the way.
+    pub fn find_ban(&self, ip: IpAddr) -> Option<IpBanEntry> {
+        let now = Utc::now().timestamp();
+        let mut expired = vec![];
+        let mut found = None;
+        for entry in &self.entries {
+            if !entry.matches(ip) {
+                continue;
+            }
+            if entry.is_active_at(now) {
+                found = Some(entry.clone());
+                break;
+            }
+            expired.push(entry.network);
+        }
+        for network in expired {
+            self.entries.remove_if(&network, |_, e| !e.is_active_at(now));
+        }
+        if found.is_some() {
+            return found;
+        }
+        let file_entries = self.file_entries.read().ok()?;
+        file_entries
+            .iter()
+            .find(|e| e.matches(ip) && e.is_active_at(now))
+            .cloned()
+    }
+
+    pub fn is_banned(&self, ip: &str) -> bool {
+        IpAddr::from_str(ip).is_ok_and(|addr| self.find_ban(addr).is_some())
+    }
+
+    ///
+    /// Loads all active bans from the `ip_ban` table.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn load_from_db(&self, db_pool: &DBPool) -> anyhow::Result<usize> {
+        let models = ip_ban::Model::find_active(db_pool, Utc::now().timestamp()).await?;
+        let mut loaded = 0;
+        for model in models {
+            match IpBanEntry::try_from(model) {
+                Ok(entry) => {
+                    self.ban(entry);
+                    loaded += 1;
+                }
+                Err(e) => error!("Skipping invalid IP ban from DB: {e}"),
+            }
+        }
+        Ok(loaded)
+    }
+
+    ///
+    /// Bans in memory and stores the ban in the `ip_ban` table.
+    ///
+    /// # Errors
+    /// - when DB is not accessible
+    pub async fn ban_and_save(&self, db_pool: &DBPool, entry: IpBanEntry) -> anyhow::Result<()> {
+        ip_ban::Model::upsert(
+            db_pool,
+            &entry.network.to_string(),
+            entry.expires_at,
+            entry.reason.clone(),
+        )
+        .await?;
+        self.ban(entry);
+        Ok(())
+    }
+
+    ///
+    /// Re-reads the ban file if it was modified since the last load.
+    /// Returns `true` when the file entries were replaced.
+    ///
+    /// # Errors
+    /// - when the file can't be read
+    pub fn reload_file(&self) -> anyhow::Result<bool> {
+        let Some(path) = &self.file else {
+            return Ok(false);
+        };
+        let modified = std::fs::metadata(path)?.modified()?;
+        {
+            let last = self
+                .file_modified
+                .lock()
+                .map_err(|_| anyhow::anyhow!("Ban file lock poisoned"))?;
+            if *last == Some(modified) {
+                return Ok(false);
+            }
+        }
+        let content = std::fs::read_to_string(path)?;
+        let entries = Self::parse_file(&content);
+        info!("Loaded {} IP bans from {}", entries.len(), path.display());
+        *self
+            .file_entries
+            .write()
+            .map_err(|_| anyhow::anyhow!("Ban file lock poisoned"))? = entries;
+        *self
+            .file_modified
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Ban file lock poisoned"))? = Some(modified);
+        Ok(true)
+    }
+
+    /// Starts polling the ban file for changes, returns `None` if no file is configured.
+    pub fn watch_file(self: &Arc<Self>) -> Option<JoinHandle<()>> {
+        self.file.as_ref()?;
+        let list = self.clone();
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(list.reload_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = list.reload_file() {
+                    error!("Failed to reload IP ban file: {e}");
+                }
+            }
+        }))
+    }
+
+    fn parse_file(content: &str) -> Vec<IpBanEntry> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                IpBanEntry::from_str(line)
+                    .inspect_err(|e| error!("Skipping invalid IP ban entry '{line}': {e}"))
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::setup_test_db;
+
+    #[test]
+    fn test_cidr_ban() {
+        let list = IpBanList::default();
+        list.ban(IpBanEntry::permanent(
+            IpNetwork::from_str("10.0.0.0/8").unwrap(),
+            Some("bots".to_string()),
+        ));
+        assert!(list.is_banned("10.1.2.3"));
+        assert!(!list.is_banned("11.1.2.3"));
+        assert!(!list.is_banned("not an ip"));
+    }
+
+    #[test]
+    fn test_expired_ban_removed() {
+        let list = IpBanList::default();
+        let now = Utc::now().timestamp();
+        let network = IpNetwork::from_str("127.0.0.1").unwrap();
+        list.ban(IpBanEntry::timed(network, now - 1, None));
+        assert!(!list.is_banned("127.0.0.1"));
+        assert!(list.unban(&network).is_none());
+        list.ban(IpBanEntry::timed(network, now + 60, None));
+        assert!(list.is_banned("127.0.0.1"));
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let entries = IpBanList::parse_file(
+            "# comment\n\n192.168.0.0/24 # lan\n1.2.3.4 1700000000\n::1 0 # local\nbad entry\n",
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].reason.as_deref(), Some("lan"));
+        assert_eq!(entries[0].expires_at, None);
+        assert_eq!(entries[1].expires_at, Some(1_700_000_000));
+        assert_eq!(entries[2].expires_at, None);
+    }
+
+    #[test]
+    fn test_reload_file() {
+        let path = std::env::temp_dir().join(format!("ip_ban_{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "172.16.0.0/12\n").unwrap();
+        let list = IpBanList::new(&IpBanConfig {
+            file: Some(path.to_string_lossy().to_string()),
+            reload_interval: 1,
+        });
+        assert!(list.reload_file().unwrap());
+        assert!(!list.reload_file().unwrap());
+        assert!(list.is_banned("172.16.5.5"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_db_bans() {
+        let db_pool = setup_test_db().await;
+        let list = IpBanList::default();
+        let now = Utc::now().timestamp();
+        let entry = IpBanEntry::timed(IpNetwork::from_str("8.8.8.0/24").unwrap(), now + 60, None);
+        list.ban_and_save(&db_pool, entry).await.unwrap();
+        ip_ban::Model::upsert(&db_pool, "9.9.9.9/32", Some(now - 60), None)
+            .await
+            .unwrap();
+        let restored = IpBanList::default();
+        assert_eq!(restored.load_from_db(&db_pool).await.unwrap(), 1);
+        assert!(restored.is_banned("8.8.8.8"));
+        assert!(!restored.is_banned("9.9.9.9"));
+    }
+}
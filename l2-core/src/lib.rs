@@ -7,9 +7,12 @@ use tokio::task::spawn_blocking;
 use tracing::instrument;
 use crate::dto::Database as DBConfig;
 
+pub mod accounts;
 pub mod constants;
 pub mod dto;
 pub mod errors;
+pub mod game_objects;
+pub mod ip_ban;
 pub mod network;
 pub mod packets;
 pub mod session;
@@ -59,7 +59,7 @@ where
 
                     broker.inbox.retain(|_, req| {
                         now.duration_since(req.sent_at)
-                            .map_or(false, |elapsed| elapsed <= broker.timeout)
+                            .is_ok_and(|elapsed| elapsed <= broker.timeout)
                     });
                     // send packet later, now we only remember it
                     let Some(req_body) = request.body.take() else {
@@ -160,6 +160,7 @@ where
     /// # Errors
     /// - if the message was not sent
     /// - if there was an error awaiting a response
+    /// - if there was no response within the broker timeout
     pub async fn send_message(
         &self,
         receiver_id: u8,
@@ -174,7 +175,7 @@ where
             id: message_id.to_string(),
         };
         self.sender.send((receiver_id, message)).await?;
-        let k = resp_rx.await?;
+        let k = timeout(self.timeout, resp_rx).await??;
         Ok(k)
     }
 }
@@ -183,6 +183,52 @@ pub enum PlayerLoginFailReasons {
     ReasonCertificationUnderwayTryAgainLater = 0x38,
 }
 
+/// Reasons of `PlayFail`, shown when the client can't enter the selected game server.
+#[repr(u8)]
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayFailReasons {
+    ReasonNoMessage = 0x00,
+    ReasonSystemErrorLoginLater = 0x01,
+    ReasonUserOrPassWrong = 0x02,
+    ReasonAccessFailedTryAgainLater = 0x04,
+    ReasonAccountInfoIncorrectContactSupport = 0x05,
+    ReasonAccountInUse = 0x07,
+    ReasonUnder18YearsKr = 0x0C,
+    ReasonServerOverloaded = 0x0F,
+    ReasonServerMaintenance = 0x10,
+    ReasonTempPassExpired = 0x11,
+    ReasonDualBox = 0x23,
+}
+
+/// Reasons of `AccountKicked`, the client shows them and closes.
+#[repr(i32)]
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKickedReasons {
+    ReasonDataStealer = 0x01,
+    ReasonGenericViolation = 0x08,
+    Reason7DaysSuspended = 0x10,
+    ReasonPermanentlyBanned = 0x20,
+}
+
+impl AccountKickedReasons {
+    /// `expires_at` is the unix timestamp when the ban ends, `i64::MAX` means it never does.
+    /// The client knows only a 7 days suspension, other temporary bans get a neutral reason.
+    #[must_use]
+    pub fn for_ban(expires_at: i64, now: i64) -> Self {
+        const DAY: i64 = 24 * 60 * 60;
+        if expires_at == i64::MAX {
+            return Self::ReasonPermanentlyBanned;
+        }
+        if (6 * DAY + 1..=7 * DAY).contains(&expires_at.saturating_sub(now)) {
+            Self::Reason7DaysSuspended
+        } else {
+            Self::ReasonGenericViolation
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GSLoginFail {
     pub buffer: SendablePacketBuffer,
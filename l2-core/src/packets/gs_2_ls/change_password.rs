@@ -1,19 +1,52 @@
-use crate::packets::common::ReadablePacket;
+use crate::packets::common::{ReadablePacket, SendablePacket};
 use crate::packets::read::ReadablePacketBuffer;
+use crate::packets::write::SendablePacketBuffer;
 
 #[derive(Clone, Debug)]
 pub struct ChangePassword {
+    buffer: SendablePacketBuffer,
     pub account: String,
     pub char_name: String,
     pub current_password: String,
     pub new_password: String,
 }
 
+impl ChangePassword {
+    ///
+    /// # Errors
+    /// - when packet size is too big
+    pub fn new(
+        account: &str,
+        char_name: &str,
+        current_password: &str,
+        new_password: &str,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+            account: account.to_string(),
+            char_name: char_name.to_string(),
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        inst.write_all()?;
+        Ok(inst)
+    }
+    fn write_all(&mut self) -> anyhow::Result<()> {
+        self.buffer.write(0x0B)?;
+        self.buffer.write_string(Some(&self.account))?;
+        self.buffer.write_string(Some(&self.char_name))?;
+        self.buffer.write_string(Some(&self.current_password))?;
+        self.buffer.write_string(Some(&self.new_password))?;
+        Ok(())
+    }
+}
+
 impl ReadablePacket for ChangePassword {
     fn read(data: &[u8]) -> Option<Self> {
         let mut buffer = ReadablePacketBuffer::new(data.to_vec());
         buffer.read_byte();
         Some(Self {
+            buffer: SendablePacketBuffer::empty(),
             account: buffer.read_string(),
             char_name: buffer.read_string(),
             current_password: buffer.read_string(),
@@ -21,3 +54,9 @@ impl ReadablePacket for ChangePassword {
         })
     }
 }
+
+impl SendablePacket for ChangePassword {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
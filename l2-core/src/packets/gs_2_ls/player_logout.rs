@@ -1,16 +1,42 @@
-use crate::packets::common::ReadablePacket;
+use crate::packets::common::{ReadablePacket, SendablePacket};
 use crate::packets::read::ReadablePacketBuffer;
+use crate::packets::write::SendablePacketBuffer;
 
 #[derive(Clone, Debug)]
 pub struct PlayerLogout {
+    buffer: SendablePacketBuffer,
     pub acc: String,
 }
 
+impl PlayerLogout {
+    ///
+    /// # Errors
+    /// - when packet size is too big
+    pub fn new(acc: &str) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+            acc: acc.to_string(),
+        };
+        inst.buffer.write(0x03)?;
+        inst.buffer.write_string(Some(acc))?;
+        Ok(inst)
+    }
+}
+
 impl ReadablePacket for PlayerLogout {
     fn read(data: &[u8]) -> Option<Self> {
         let mut buffer = ReadablePacketBuffer::new(data.to_vec());
         buffer.read_byte();
         let acc = buffer.read_string();
-        Some(Self { acc })
+        Some(Self {
+            buffer: SendablePacketBuffer::empty(),
+            acc,
+        })
+    }
+}
+
+impl SendablePacket for PlayerLogout {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
     }
 }
@@ -14,26 +14,28 @@ pub struct ReplyChars {
 }
 
 impl ReplyChars {
-    #[allow(clippy::cast_possible_truncation)]
+    /// `char_deletion_timestamps` are unix timestamps (in seconds) of characters pending deletion.
     pub fn new(account_name: String, chars: &[character::Model]) -> ReplyChars {
-        let mut chars_to_del_list = vec![];
-        for ch in chars {
-            if let Some(del_at) = ch.delete_at {
-                chars_to_del_list.push(del_at.timestamp());
-            }
-        }
+        let chars_to_del_list: Vec<i64> = chars
+            .iter()
+            .filter_map(|ch| ch.delete_at.map(|del_at| del_at.timestamp()))
+            .collect();
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
             account_name,
-            chars: 0,
-            delete_chars_len: 0,
+            chars: u8::try_from(chars.len()).unwrap_or(u8::MAX),
+            delete_chars_len: u8::try_from(chars_to_del_list.len()).unwrap_or(u8::MAX),
             char_deletion_timestamps: chars_to_del_list,
         };
         inst.buffer.write(0x08).unwrap();
         inst.buffer.write_string(Some(&inst.account_name)).unwrap();
         inst.buffer.write(inst.chars).unwrap();
         inst.buffer.write(inst.delete_chars_len).unwrap();
-        for ch in &inst.char_deletion_timestamps {
+        for ch in inst
+            .char_deletion_timestamps
+            .iter()
+            .take(usize::from(inst.delete_chars_len))
+        {
             inst.buffer.write_i64(*ch).unwrap();
         }
         inst
@@ -68,3 +70,52 @@ impl SendablePacket for ReplyChars {
         &mut self.buffer
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{new_char, setup_test_db};
+    use entities::entities::user;
+
+    #[tokio::test]
+    async fn test_reply_chars_round_trip() {
+        let db_pool = setup_test_db().await;
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let bob = character::Model::create(&db_pool, new_char("Bob", user.id), vec![])
+            .await
+            .unwrap();
+        let alice = character::Model::create(&db_pool, new_char("Alice", user.id), vec![])
+            .await
+            .unwrap();
+        let delete_at = chrono::DateTime::from_timestamp(1_735_689_600, 0)
+            .unwrap()
+            .fixed_offset();
+        let alice = alice
+            .set_delete_at(&db_pool, Some(delete_at))
+            .await
+            .unwrap();
+        let mut packet = ReplyChars::new("admin".to_string(), &[bob, alice]);
+        assert_eq!(packet.chars, 2);
+        assert_eq!(packet.delete_chars_len, 1);
+        let data = packet.get_buffer_mut().get_data_mut();
+        // skip the packet size
+        let read = ReplyChars::read(&data[2..]).unwrap();
+        assert_eq!(read.account_name, "admin");
+        assert_eq!(read.chars, 2);
+        assert_eq!(read.delete_chars_len, 1);
+        assert_eq!(read.char_deletion_timestamps, vec![1_735_689_600]);
+    }
+
+    #[test]
+    fn test_reply_chars_without_chars() {
+        let mut packet = ReplyChars::new("admin".to_string(), &[]);
+        let data = packet.get_buffer_mut().get_data_mut();
+        let read = ReplyChars::read(&data[2..]).unwrap();
+        assert_eq!(read.account_name, "admin");
+        assert_eq!(read.chars, 0);
+        assert_eq!(read.delete_chars_len, 0);
+        assert!(read.char_deletion_timestamps.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::packets::common::{ReadablePacket, SendablePacket};
+use crate::packets::read::ReadablePacketBuffer;
+use crate::packets::write::SendablePacketBuffer;
+
+/// The result of `gs_2_ls::ChangePassword`, the message is shown to the character as is.
+#[derive(Clone, Debug)]
+pub struct ChangePassword {
+    buffer: SendablePacketBuffer,
+    pub char_name: String,
+    pub message: String,
+}
+
+impl ChangePassword {
+    pub const SUCCESS: &'static str = "You have successfully changed your password!";
+    pub const WRONG_PASSWORD: &'static str =
+        "The typed current password doesn't match with your current one.";
+    pub const INVALID_PASSWORD: &'static str = "The new password is too short or too long.";
+    pub const FAILED: &'static str = "The password change was unsuccessful!";
+
+    ///
+    /// # Errors
+    /// - when packet size is too big
+    pub fn new(char_name: &str, message: &str) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+            char_name: char_name.to_string(),
+            message: message.to_string(),
+        };
+        inst.write_all()?;
+        Ok(inst)
+    }
+    fn write_all(&mut self) -> anyhow::Result<()> {
+        self.buffer.write(0x06)?;
+        self.buffer.write_string(Some(&self.char_name))?;
+        self.buffer.write_string(Some(&self.message))?;
+        Ok(())
+    }
+}
+
+impl ReadablePacket for ChangePassword {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        buffer.read_byte();
+        Some(Self {
+            buffer: SendablePacketBuffer::empty(),
+            char_name: buffer.read_string(),
+            message: buffer.read_string(),
+        })
+    }
+}
+
+impl SendablePacket for ChangePassword {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::packets::gs_2_ls;
+
+    #[test]
+    fn test_request_and_response_round_trip() {
+        let mut request = gs_2_ls::ChangePassword::new("admin", "hero", "old", "new").unwrap();
+        let data = request.get_buffer_mut().get_data();
+        let request = gs_2_ls::ChangePassword::read(&data[2..]).unwrap();
+        assert_eq!(request.account, "admin");
+        assert_eq!(request.char_name, "hero");
+        assert_eq!(request.current_password, "old");
+        assert_eq!(request.new_password, "new");
+
+        let mut response = ChangePassword::new("hero", ChangePassword::SUCCESS).unwrap();
+        let data = response.get_buffer_mut().get_data();
+        assert_eq!(data[2], 0x06);
+        let response = ChangePassword::read(&data[2..]).unwrap();
+        assert_eq!(response.char_name, "hero");
+        assert_eq!(response.message, ChangePassword::SUCCESS);
+    }
+}
@@ -1,11 +1,12 @@
 mod auth_gs;
+mod change_password;
 mod init_ls;
 mod kick_player;
 mod player_auth_response;
 mod request_chars;
 
 pub use self::{
-    auth_gs::AuthGS, init_ls::InitLS, kick_player::KickPlayer,
+    auth_gs::AuthGS, change_password::ChangePassword, init_ls::InitLS, kick_player::KickPlayer,
     player_auth_response::PlayerAuthResponse, request_chars::RequestChars,
 };
 
@@ -1,12 +1,14 @@
+use entities::entities::character;
 use migration::{Migrator, MigratorTrait};
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{ActiveValue, ConnectOptions, Database, DatabaseConnection};
 use tokio::sync::OnceCell;
 
 pub type DBPool = DatabaseConnection;
 
 static DB_POOL: OnceCell<DBPool> = OnceCell::const_new();
 
-async fn setup_test_db() -> DBPool {
+/// Creates a new, isolated in-memory DB, use it when a test needs its own data.
+pub async fn setup_test_db() -> DBPool {
     let opt = ConnectOptions::new("sqlite::memory:");
 
     let conn = Database::connect(opt)
@@ -21,3 +23,21 @@ async fn setup_test_db() -> DBPool {
 pub async fn get_test_db() -> DBPool {
     DB_POOL.get_or_init(setup_test_db).await.clone()
 }
+
+/// A level 1 human fighter of the given user, ready to be inserted.
+#[must_use]
+pub fn new_char(name: &str, user_id: i32) -> character::ActiveModel {
+    character::ActiveModel {
+        name: ActiveValue::Set(name.to_string()),
+        level: ActiveValue::Set(1),
+        user_id: ActiveValue::Set(user_id),
+        sex: ActiveValue::Set(0),
+        x: ActiveValue::Set(0),
+        y: ActiveValue::Set(0),
+        z: ActiveValue::Set(0),
+        race_id: ActiveValue::Set(0),
+        base_class_id: ActiveValue::Set(0),
+        transform_id: ActiveValue::Set(0),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use entities::entities::user;
+use entities::DBPool;
+use std::fmt::Debug;
+
+/// Result of the password check done by an [`AccountProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Credentials {
+    Valid,
+    WrongPassword,
+    UnknownAccount,
+}
+
+///
+/// Source of truth for account passwords. The login server still keeps a row in the local
+/// `user` table for every account (bans, access level, characters), the provider only decides
+/// whether the password is correct and creates accounts when auto registration is on.
+///
+#[async_trait]
+pub trait AccountProvider: Send + Sync + Debug {
+    ///
+    /// `account` is the local row of the account if it exists.
+    ///
+    /// # Errors
+    /// - when the backend is not reachable or answers with garbage
+    async fn check_password(
+        &self,
+        account: Option<&user::Model>,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<Credentials>;
+
+    ///
+    /// Creates the account in the backend and returns the local row of it.
+    ///
+    /// # Errors
+    /// - when the backend refused to create the account
+    /// - when DB is not accessible
+    async fn register(
+        &self,
+        db_pool: &DBPool,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<user::Model>;
+
+    ///
+    /// Replaces the password of the account, `current_password` must be the valid one.
+    /// Returns `WrongPassword` when it is not and `UnknownAccount` when the backend
+    /// doesn't know the account.
+    ///
+    /// # Errors
+    /// - when the backend is not reachable or refused the change
+    /// - when DB is not accessible
+    async fn change_password(
+        &self,
+        db_pool: &DBPool,
+        account: user::Model,
+        current_password: &str,
+        new_password: &str,
+    ) -> anyhow::Result<Credentials>;
+}
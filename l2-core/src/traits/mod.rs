@@ -1,3 +1,4 @@
+pub mod accounts;
 pub mod handlers;
 pub mod server;
 
@@ -1,25 +1,34 @@
+use crate::client_thread::ClientSender;
 use crate::controller::Login;
+use crate::dto::player;
 use crate::packet::cp_factory::build_client_packet;
-use crate::packet::to_client::Init;
+use crate::packet::to_client::{Init, LoginOk, ServerList};
 use anyhow::{bail, Error};
 use async_trait::async_trait;
+use entities::entities::user;
 use entities::DBPool;
 use l2_core::config::login::LoginServer;
 use l2_core::crypt::login::Encryption;
 use l2_core::crypt::{generate_blowfish_key, rsa};
 use l2_core::dto::InboundConnection;
 use l2_core::errors::Packet;
+use l2_core::packets::common::PlayerLoginFail;
+use l2_core::packets::error::PacketRun;
 use l2_core::session::SessionKey;
 use l2_core::traits::handlers::{InboundHandler, PacketHandler, PacketSender};
 use l2_core::traits::Shutdown;
 use std::net::{IpAddr, Ipv4Addr};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tokio::sync::{Mutex, Notify};
 use tracing::{error, info, instrument};
 
+/// How long a client has to reach the game server after `PlayOk`.
+const GS_JOIN_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Clone, Debug)]
 pub struct Client {
     blowfish_key: Vec<u8>,
@@ -35,6 +44,7 @@ pub struct Client {
     timeout: u8,
     session_key: SessionKey,
     rsa_key_pair: rsa::ScrambledRSAKeyPair,
+    is_play_ok_sent: bool,
 }
 
 /// # This function called each time when there is a new connection.
@@ -64,6 +74,47 @@ impl Client {
     pub fn get_session_key(&self) -> &SessionKey {
         &self.session_key
     }
+    pub fn get_sender(&self) -> ClientSender {
+        ClientSender {
+            tcp_writer: self.tcp_writer.clone(),
+            shutdown_notifier: self.shutdown_notifier.clone(),
+        }
+    }
+    /// After `PlayOk` the client leaves the login server on its own, the player must be kept.
+    pub fn set_play_ok_sent(&mut self) {
+        self.is_play_ok_sent = true;
+    }
+
+    /// Called once the account credentials are verified: registers the player in the login
+    /// controller and answers with `LoginOk` (or `ServerList` when licence is not shown).
+    pub async fn complete_login(&mut self, user: &user::Model) -> Result<(), PacketRun> {
+        let account_name = user.username.as_str();
+        self.account_name = Some(account_name.to_string());
+        let player_info = player::Info {
+            is_authed: true,
+            session: Some(self.session_key.clone()),
+            account_name: account_name.to_string(),
+            ip_address: Some(IpAddr::V4(self.ip)),
+            last_server: u8::try_from(user.last_server).unwrap_or_default(),
+            access_level: user.access_level,
+            login_client: Some(self.get_sender()),
+            ..Default::default()
+        };
+        let lc = self.lc.clone();
+        if let Err(err) = lc.on_player_login(player_info).await {
+            let err_msg = format!("Player login failed: {err:?}");
+            self.send_packet(Box::new(PlayerLoginFail::new(err))).await?;
+            return Err(PacketRun { msg: Some(err_msg) });
+        }
+        if lc.get_config().client.show_licence {
+            self.send_packet(Box::new(LoginOk::new(&self.session_key)))
+                .await?;
+        } else {
+            let s_list = ServerList::new(self, account_name);
+            self.send_packet(Box::new(s_list)).await?;
+        }
+        Ok(())
+    }
     fn get_ipv4_from_socket(socket: &TcpStream) -> Ipv4Addr {
         let default = Ipv4Addr::new(127, 0, 0, 1);
         match socket.peer_addr() {
@@ -112,6 +163,7 @@ impl PacketHandler for Client {
             session_key: SessionKey::new(),
             account_name: None,
             rsa_key_pair: lc.get_random_rsa_key_pair(),
+            is_play_ok_sent: false,
             blowfish_key,
             timeout,
             lc,
@@ -138,6 +190,18 @@ impl PacketHandler for Client {
 
     fn on_disconnect(&mut self) {
         info!("Player disconnected: {:?}", self.session_id);
+        if let Some(account_name) = &self.account_name {
+            if self.is_play_ok_sent {
+                // the client is on its way to the game server
+                self.lc.expire_player_session(
+                    account_name,
+                    self.session_key.clone(),
+                    GS_JOIN_TIMEOUT,
+                );
+            } else {
+                self.lc.remove_player_session(account_name, &self.session_key);
+            }
+        }
     }
     fn get_stream_reader_mut(&self) -> &Arc<Mutex<OwnedReadHalf>> {
         &self.tcp_reader
@@ -1,2 +1,4 @@
 mod handler;
+mod sender;
 pub use handler::Client as ClientHandler;
+pub use sender::ClientSender;
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use l2_core::crypt::login::Encryption;
+use l2_core::traits::handlers::PacketSender;
+use l2_core::traits::Shutdown;
+use std::sync::Arc;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::{Mutex, Notify};
+
+/// Lets the login controller send packets to a client which is still on the login server,
+/// e.g. to tell it that the account was logged in from somewhere else, and disconnect it.
+#[derive(Debug, Clone)]
+pub struct ClientSender {
+    pub(crate) tcp_writer: Arc<Mutex<OwnedWriteHalf>>,
+    pub(crate) shutdown_notifier: Arc<Notify>,
+}
+
+impl Shutdown for ClientSender {
+    fn get_shutdown_listener(&self) -> Arc<Notify> {
+        self.shutdown_notifier.clone()
+    }
+}
+
+#[async_trait]
+impl PacketSender for ClientSender {
+    fn encryption(&self) -> Option<&Encryption> {
+        None
+    }
+
+    async fn get_stream_writer_mut(&self) -> &Arc<Mutex<OwnedWriteHalf>> {
+        &self.tcp_writer
+    }
+}
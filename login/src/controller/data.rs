@@ -1,10 +1,14 @@
 use crate::dto::game_server::GSInfo;
 use crate::dto::player;
+use super::security::FailedLogins;
 use dashmap::DashMap;
 use l2_core::config::login;
 use l2_core::crypt::rsa::{generate_rsa_key_pair, ScrambledRSAKeyPair};
+use l2_core::accounts::new_account_provider;
+use l2_core::ip_ban::IpBanList;
 use l2_core::message_broker::MessageBroker;
 use l2_core::packets::common::PacketType;
+use l2_core::traits::accounts::AccountProvider;
 use rand::Rng;
 use std::sync::Arc;
 use std::time::Duration;
@@ -15,8 +19,11 @@ pub struct Login {
     key_pairs: Vec<ScrambledRSAKeyPair>,
     pub(super) config: Arc<login::LoginServer>,
     pub(super) game_servers: DashMap<u8, GSInfo>,
-    pub(super) ip_ban_list: DashMap<String, i64>,
+    pub(super) ip_ban_list: Arc<IpBanList>,
+    pub(super) account_provider: Arc<dyn AccountProvider>,
     pub(super) players: DashMap<String, player::Info>,
+    pub(super) failed_logins_by_ip: DashMap<String, FailedLogins>,
+    pub(super) failed_logins_by_account: DashMap<String, FailedLogins>,
     pub message_broker: Arc<MessageBroker<u8, PacketType>>,
 }
 
@@ -28,9 +35,12 @@ impl Login {
             Duration::from_secs(u64::from(config.listeners.game_servers.messages.timeout));
         Login {
             key_pairs: Login::generate_rsa_key_pairs(10),
+            ip_ban_list: Arc::new(IpBanList::new(&config.ip_ban)),
+            account_provider: new_account_provider(&config.accounts),
             config,
-            ip_ban_list: DashMap::new(),
             players: DashMap::new(),
+            failed_logins_by_ip: DashMap::new(),
+            failed_logins_by_account: DashMap::new(),
             game_servers: DashMap::new(),
             message_broker: MessageBroker::new(threshold),
         }
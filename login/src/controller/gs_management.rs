@@ -1,6 +1,6 @@
 use super::data::Login;
 use crate::dto::game_server::GSInfo;
-use l2_core::packets::common::{GSLoginFailReasons, ServerData, ServerStatus};
+use l2_core::packets::common::{GSLoginFailReasons, PlayFailReasons, ServerData, ServerStatus};
 use std::net::Ipv4Addr;
 
 impl Login {
@@ -12,11 +12,11 @@ impl Login {
                 port: i32::from(s.get_port()),
                 age_limit: i32::from(s.get_age_limit()),
                 pvp: s.is_pvp(),
-                current_players: 0,               //todo: implement me
+                current_players: s.get_current_players(),
                 max_players: s.get_max_players(), //allow wrapping
                 brackets: s.show_brackets(),
                 clock: false, //todo: implement me
-                status: ServerStatus::try_from(s.get_status()).ok(),
+                status: s.get_server_status(),
                 server_id: i32::from(s.get_id()),
                 server_type: s.get_server_type(),
             });
@@ -24,6 +24,48 @@ impl Login {
         servers
     }
 
+    ///
+    /// Decides whether the account may enter the game server it selected in the server list.
+    /// The server must be registered and authed, and must not be `Down`.
+    /// Accounts with positive `access_level` (GMs) can join servers in `GmOnly` mode or full ones.
+    /// There is no birth date stored for the account, so the age limit is only shown to the client.
+    ///
+    pub fn check_gs_login(
+        &self,
+        account_name: &str,
+        server_id: u8,
+    ) -> anyhow::Result<(), PlayFailReasons> {
+        let player = self
+            .get_player(account_name)
+            .ok_or(PlayFailReasons::ReasonAccessFailedTryAgainLater)?;
+        let gs = self
+            .get_game_server(server_id)
+            .filter(GSInfo::is_authed)
+            .ok_or(PlayFailReasons::ReasonAccessFailedTryAgainLater)?;
+        let chars = player
+            .chars_on_servers
+            .get(&server_id)
+            .map_or(0, |c| c.total_chars);
+        if chars > self.config.max_chars_on_account {
+            return Err(PlayFailReasons::ReasonAccountInfoIncorrectContactSupport);
+        }
+        let is_gm = player.access_level > 0;
+        match gs.get_server_status() {
+            None | Some(ServerStatus::Down) => Err(PlayFailReasons::ReasonServerMaintenance),
+            Some(ServerStatus::GmOnly) if !is_gm => Err(PlayFailReasons::ReasonServerMaintenance),
+            Some(ServerStatus::Full) if !is_gm => Err(PlayFailReasons::ReasonServerOverloaded),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remembers the server picked by the client, `PlayerAuthRequest` must come from it.
+    pub fn on_gs_selected(&self, account_name: &str, server_id: u8) -> bool {
+        self.with_player(account_name, |pl| {
+            pl.selected_server = Some(server_id);
+            true
+        })
+    }
+
     pub fn with_gs<F>(&self, gs_id: u8, f: F) -> bool
     where
         F: Fn(&mut GSInfo),
@@ -57,7 +99,115 @@ impl Login {
         Ok(id)
     }
 
+    /// Recounts the accounts which joined the game server and stores the value in `GSInfo`.
+    pub fn update_gs_online_count(&self, gs_id: u8) {
+        let online = self
+            .players
+            .iter()
+            .filter(|p| p.game_server == Some(gs_id) && p.is_joined_gs)
+            .count();
+        self.with_gs(gs_id, |gs| {
+            gs.set_current_players(u32::try_from(online).unwrap_or(u32::MAX));
+        });
+    }
+
     pub fn remove_gs(&self, server_id: u8) {
         self.game_servers.remove(&server_id);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dto::player;
+    use l2_core::config::login::LoginServer;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+
+    fn login_with_gs(status: ServerStatus, max_players: u32) -> Login {
+        let cfg = LoginServer::from_string(include_str!("../test_data/test_config.yaml"));
+        let lc = Login::new(Arc::new(cfg));
+        let gs = GSInfo::new(
+            1,
+            false,
+            false,
+            7777,
+            true,
+            status as i32,
+            false,
+            1,
+            0,
+            false,
+            max_players,
+            vec![],
+            &["127.0.0.1/32".to_string(), "127.0.0.1".to_string()],
+        )
+        .unwrap();
+        lc.game_servers.insert(1, gs);
+        for (account, access_level) in [("player", 0), ("gm", 100)] {
+            lc.players.insert(
+                account.to_string(),
+                player::Info {
+                    account_name: account.to_string(),
+                    access_level,
+                    is_authed: true,
+                    ..player::Info::default()
+                },
+            );
+        }
+        lc
+    }
+
+    #[tokio::test]
+    async fn test_gm_only_server() {
+        let lc = login_with_gs(ServerStatus::GmOnly, 100);
+        assert!(matches!(
+            lc.check_gs_login("player", 1),
+            Err(PlayFailReasons::ReasonServerMaintenance)
+        ));
+        assert!(lc.check_gs_login("gm", 1).is_ok());
+        assert!(matches!(
+            lc.check_gs_login("player", 2),
+            Err(PlayFailReasons::ReasonAccessFailedTryAgainLater)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_server_selection() {
+        let lc = login_with_gs(ServerStatus::Auto, 100);
+        lc.with_gs(1, |gs| gs.set_server_status(ServerStatus::Down as i32));
+        assert!(matches!(
+            lc.check_gs_login("player", 1),
+            Err(PlayFailReasons::ReasonServerMaintenance)
+        ));
+        lc.with_gs(1, |gs| gs.set_server_status(ServerStatus::Auto as i32));
+        lc.with_player("player", |pl| {
+            pl.chars_on_servers.insert(
+                1,
+                player::GSCharsInfo {
+                    total_chars: lc.get_config().max_chars_on_account + 1,
+                    ..player::GSCharsInfo::default()
+                },
+            );
+            true
+        });
+        assert!(matches!(
+            lc.check_gs_login("player", 1),
+            Err(PlayFailReasons::ReasonAccountInfoIncorrectContactSupport)
+        ));
+        assert!(lc.on_gs_selected("gm", 1));
+        assert_eq!(lc.get_player("gm").unwrap().selected_server, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_full_server() {
+        let lc = login_with_gs(ServerStatus::Auto, 1);
+        assert!(lc.check_gs_login("player", 1).is_ok());
+        lc.with_gs(1, |gs| gs.set_current_players(1));
+        assert!(matches!(
+            lc.check_gs_login("player", 1),
+            Err(PlayFailReasons::ReasonServerOverloaded)
+        ));
+        assert!(lc.check_gs_login("gm", 1).is_ok());
+    }
+}
@@ -1,20 +1,182 @@
 use super::data::Login;
-use l2_core::packets::common::{PacketType, PlayerLoginFailReasons};
+use l2_core::hash_password;
+use l2_core::traits::accounts::Credentials;
+use l2_core::packets::common::{
+    AccountKickedReasons, PacketType, PlayerLoginFail, PlayerLoginFailReasons,
+};
+use l2_core::traits::handlers::PacketSender;
+use l2_core::traits::Shutdown;
+use l2_core::packets::gs_2_ls::ReplyChars;
 use l2_core::packets::ls_2_gs::{KickPlayer, RequestChars};
+use l2_core::session::SessionKey;
 use crate::dto::player;
 use crate::dto::player::GSCharsInfo;
+use chrono::Utc;
+use entities::entities::user;
+use entities::DBPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
-use tracing::info;
+use tracing::{error, info};
 
 impl Login {
+    /// Checks account credentials, unknown accounts are created when `auto_registration` is on.
+    /// Shared by all the client packets which carry a username and a password.
+    /// Wrong credentials are counted to lock the account or ban the IP (see `brute_force` config).
+    pub async fn authenticate(
+        &self,
+        db_pool: &DBPool,
+        ip: &str,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<user::Model, PlayerLoginFailReasons> {
+        let username = user::Model::normalize_username(username);
+        self.check_account_lock(&username)?;
+        let user = self.find_or_register(db_pool, &username, password).await;
+        match user {
+            Err(PlayerLoginFailReasons::ReasonUserOrPassWrong) => {
+                self.on_login_failed(db_pool, ip, &username).await;
+            }
+            Ok(_) => self.on_login_succeeded(ip, &username),
+            Err(_) => {}
+        }
+        user
+    }
+
+    async fn find_or_register(
+        &self,
+        db_pool: &DBPool,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<user::Model, PlayerLoginFailReasons> {
+        let user_option = user::Model::find_some_by_username(db_pool, username)
+            .await
+            .map_err(|e| {
+                error!("Failed to load account {username}: {e}");
+                PlayerLoginFailReasons::ReasonSystemErrorLoginLater
+            })?;
+        let credentials = self
+            .account_provider
+            .check_password(user_option.as_ref(), username, password)
+            .await
+            .map_err(|e| {
+                error!("Failed to check password of {username}: {e}");
+                PlayerLoginFailReasons::ReasonSystemErrorLoginLater
+            })?;
+        let user_option = match (credentials, user_option) {
+            (Credentials::WrongPassword, _) => {
+                return Err(PlayerLoginFailReasons::ReasonUserOrPassWrong)
+            }
+            (Credentials::Valid, None) => {
+                // known to the backend only, the local row keeps bans and characters
+                if !user::Model::is_valid_username(username) {
+                    return Err(PlayerLoginFailReasons::ReasonUserOrPassWrong);
+                }
+                let user = user::Model::register(db_pool, username, String::new())
+                    .await
+                    .map_err(|e| {
+                        error!("Failed to create local account {username}: {e}");
+                        PlayerLoginFailReasons::ReasonSystemErrorLoginLater
+                    })?;
+                Some(user)
+            }
+            (Credentials::Valid, Some(user)) if user.is_legacy_hash() => {
+                Some(Self::rehash_legacy_password(db_pool, user, password).await)
+            }
+            (Credentials::Valid, user) => user,
+            (Credentials::UnknownAccount, _) => None,
+        };
+        if let Some(user) = user_option {
+            if user.access_level < 0 {
+                info!("Blocked account tried to login: {username}");
+                return Err(PlayerLoginFailReasons::ReasonAccountSuspendedCall);
+            }
+            if user.is_banned_at(Utc::now().timestamp()) {
+                info!("Banned account tried to login: {username}");
+                return Err(PlayerLoginFailReasons::ReasonAccountSuspendedCall);
+            }
+            if user.ban_duration.is_some() {
+                info!("Ban expired, unbanning account: {username}");
+                return user.lift_ban(db_pool).await.map_err(|e| {
+                    error!("Failed to lift ban of {username}: {e}");
+                    PlayerLoginFailReasons::ReasonSystemErrorLoginLater
+                });
+            }
+            return Ok(user);
+        }
+        // the name rules apply to new accounts only, older ones may not follow them
+        if !self.config.auto_registration || !user::Model::is_valid_username(username) {
+            return Err(PlayerLoginFailReasons::ReasonUserOrPassWrong);
+        }
+        self.account_provider
+            .register(db_pool, username, password)
+            .await
+            .map_err(|e| {
+                error!("Failed to register account {username}: {e}");
+                PlayerLoginFailReasons::ReasonSystemErrorLoginLater
+            })
+    }
+
+    /// Changes the password in the account backend, asked by a game server for its player.
+    pub async fn change_password(
+        &self,
+        db_pool: &DBPool,
+        account: user::Model,
+        current_password: &str,
+        new_password: &str,
+    ) -> anyhow::Result<Credentials> {
+        self.account_provider
+            .change_password(db_pool, account, current_password, new_password)
+            .await
+    }
+
+    /// Reason shown to a banned account, it depends on how long the ban lasts.
+    pub async fn ban_kick_reason(db_pool: &DBPool, username: &str) -> AccountKickedReasons {
+        let username = user::Model::normalize_username(username);
+        let expires_at = user::Model::find_some_by_username(db_pool, &username)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|user| user.ban_duration)
+            .unwrap_or(i64::MAX);
+        AccountKickedReasons::for_ban(expires_at, Utc::now().timestamp())
+    }
+
+    /// Replaces a hash imported from L2J with argon2, the password is known only at login.
+    async fn rehash_legacy_password(
+        db_pool: &DBPool,
+        user: user::Model,
+        password: &str,
+    ) -> user::Model {
+        let password_hash = match hash_password(password).await {
+            Ok(password_hash) => password_hash,
+            Err(e) => {
+                error!("Failed to hash password of {}: {e}", user.username);
+                return user;
+            }
+        };
+        match user.clone().set_password_hash(db_pool, password_hash).await {
+            Ok(user) => {
+                info!("Legacy password hash of {} upgraded", user.username);
+                user
+            }
+            Err(e) => {
+                error!("Failed to upgrade password hash of {}: {e}", user.username);
+                user
+            }
+        }
+    }
+
     pub async fn on_player_login(
         &self,
         mut player_info: player::Info,
     ) -> anyhow::Result<(), PlayerLoginFailReasons> {
         let account_name = player_info.account_name.clone();
+        self.check_dual_box(&player_info)?;
         self.check_player_in_game(&account_name).await?;
         let mut task_results = self.message_broker
             .send_message_to_all(&account_name, || Box::new(RequestChars::new(&account_name)))
@@ -23,14 +185,9 @@ impl Login {
 
         while let Some(Ok(resp)) = task_results.next() {
             if let Some((gs_id, PacketType::ReplyChars(p))) = resp {
-                player_info.chars_on_servers.insert(
-                    gs_id,
-                    GSCharsInfo {
-                        char_deletion_timestamps: p.char_deletion_timestamps,
-                        chars_to_delete: p.delete_chars_len,
-                        total_chars: p.chars,
-                    },
-                );
+                player_info
+                    .chars_on_servers
+                    .insert(gs_id, GSCharsInfo::from(&p));
             }
             // ignore all the tasks that are timed out
         }
@@ -38,12 +195,46 @@ impl Login {
         Ok(())
     }
 
+    /// Counts other accounts online from the same IP, the limit is set by `dual_box` config.
+    fn check_dual_box(&self, player_info: &player::Info) -> Result<(), PlayerLoginFailReasons> {
+        let Some(ip) = player_info.ip_address else {
+            return Ok(());
+        };
+        let Some(limit) = self.config.dual_box.limit_for(&ip) else {
+            return Ok(());
+        };
+        let online = self
+            .players
+            .iter()
+            .filter(|p| p.key() != &player_info.account_name && p.ip_address == Some(ip))
+            .count();
+        if online < limit as usize {
+            return Ok(());
+        }
+        info!(
+            "Account {} rejected, {online} accounts are already online from {ip}",
+            player_info.account_name
+        );
+        if limit >= 10 {
+            Err(PlayerLoginFailReasons::ReasonAlreadyLoggedInto10Accounts)
+        } else {
+            Err(PlayerLoginFailReasons::ReasonDualBox)
+        }
+    }
+
     async fn check_player_in_game(
         &self,
         account_name: &str,
     ) -> anyhow::Result<(), PlayerLoginFailReasons> {
         if let Some(player_in_game) = self.players.remove(account_name) {
+            if let Some(login_client) = &player_in_game.1.login_client {
+                // the old session may still be on the server list, the client closes on this packet
+                let packet = PlayerLoginFail::new(PlayerLoginFailReasons::ReasonAccountInUse);
+                let _ = login_client.send_packet(Box::new(packet)).await;
+                login_client.shutdown();
+            }
             if let Some(gs) = player_in_game.1.game_server {
+                self.update_gs_online_count(gs);
                 let _ = self.message_broker
                     .notify(gs, Box::new(KickPlayer::new(account_name)))
                     .await;
@@ -57,6 +248,37 @@ impl Login {
         Ok(())
     }
 
+    /// Character counts sent by a game server on its own, e.g. after a character was deleted.
+    pub fn on_chars_changed(&self, gs_id: u8, chars: &ReplyChars) {
+        self.with_player(&chars.account_name, |pl| {
+            pl.chars_on_servers.insert(gs_id, GSCharsInfo::from(chars));
+            true
+        });
+    }
+
+    /// Accepts the player coming to the game server picked in the server list, the session
+    /// must match. The pick is used up, so entering a server again needs a new one.
+    pub fn on_player_auth(&self, account_name: &str, gs_id: u8, session: &SessionKey) -> bool {
+        let show_license = self.config.client.show_licence;
+        self.with_player(account_name, |pl| {
+            if pl.selected_server != Some(gs_id) {
+                return false; // the client went to another world than it picked
+            }
+            if !pl
+                .session
+                .as_ref()
+                .is_some_and(|s| s.equals(session, show_license))
+            {
+                return false;
+            }
+            pl.selected_server = None;
+            pl.game_server = Some(gs_id);
+            pl.is_joined_gs = true;
+            pl.last_server = gs_id;
+            true
+        })
+    }
+
     pub fn on_player_logout(&self, account_name: &str) {
         info!("Player logged out: {account_name}");
         self.remove_player(account_name);
@@ -76,7 +298,44 @@ impl Login {
         }
     }
     pub fn remove_player(&self, account_name: &str) {
-        self.players.remove(account_name);
+        if let Some((_, player)) = self.players.remove(account_name) {
+            if let Some(gs_id) = player.game_server {
+                self.update_gs_online_count(gs_id);
+            }
+        }
+    }
+
+    /// Removes the player only if it still belongs to the given login session,
+    /// a newer login of the same account is kept.
+    pub fn remove_player_session(&self, account_name: &str, session: &SessionKey) {
+        let removed = self.players.remove_if(account_name, |_, pl| {
+            !pl.is_joined_gs
+                && pl
+                    .session
+                    .as_ref()
+                    .is_some_and(|s| s.equals(session, true))
+        });
+        if let Some((_, player)) = removed {
+            if let Some(gs_id) = player.game_server {
+                self.update_gs_online_count(gs_id);
+            }
+        }
+    }
+
+    /// Removes the session after `delay` unless the player reached a game server by then,
+    /// a client which got `PlayOk` but never joined would count for the dual box limit forever.
+    pub fn expire_player_session(
+        self: &Arc<Self>,
+        account_name: &str,
+        session: SessionKey,
+        delay: Duration,
+    ) -> JoinHandle<()> {
+        let lc = self.clone();
+        let account_name = account_name.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            lc.remove_player_session(&account_name, &session);
+        })
     }
 
     pub fn remove_all_gs_players(&self, gs_id: u8) {
@@ -109,6 +368,7 @@ impl Login {
                 self.players.insert(acc_name.clone(), pl);
             }
         }
+        self.update_gs_online_count(gs_id);
     }
 
     pub fn generate_session_id<T>() -> T
@@ -119,3 +379,309 @@ impl Login {
         rng.gen()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::config::login::LoginServer;
+    use crate::dto::game_server::GSInfo;
+    use l2_core::packets::common::ServerStatus;
+    use entities::entities::character;
+    use l2_core::tests::{new_char, setup_test_db};
+    use std::net::{IpAddr, Ipv4Addr};
+    use sea_orm::{ActiveModelTrait, ActiveValue};
+    use l2_core::traits::{IpBan, ServerConfig};
+    use std::sync::Arc;
+
+    async fn create_user(db_pool: &DBPool, username: &str, ban_duration: Option<i64>) {
+        user::ActiveModel {
+            id: ActiveValue::NotSet,
+            username: ActiveValue::Set(username.to_string()),
+            password: ActiveValue::Set(hash_password("secret").await.unwrap()),
+            access_level: ActiveValue::Set(0),
+            ban_duration: ActiveValue::Set(ban_duration),
+            ban_ip: ActiveValue::Set(ban_duration.map(|_| "127.0.0.1".to_string())),
+            pi_agreement: ActiveValue::Set(false),
+            last_server: ActiveValue::Set(0),
+            pin: ActiveValue::NotSet,
+        }
+        .insert(db_pool)
+        .await
+        .unwrap();
+    }
+
+    fn login_controller() -> Login {
+        let cfg = LoginServer::from_string(include_str!("../test_data/test_config.yaml"));
+        Login::new(Arc::new(cfg))
+    }
+
+    #[tokio::test]
+    async fn test_banned_account_is_rejected() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "banned", Some(Utc::now().timestamp() + 3600)).await;
+        let res = lc.authenticate(&db_pool, "127.0.0.1", "banned", "secret").await;
+        assert!(matches!(
+            res,
+            Err(PlayerLoginFailReasons::ReasonAccountSuspendedCall)
+        ));
+        assert_eq!(
+            Login::ban_kick_reason(&db_pool, "banned").await,
+            AccountKickedReasons::ReasonGenericViolation
+        );
+        create_user(&db_pool, "week", Some(Utc::now().timestamp() + 7 * 24 * 3600)).await;
+        assert_eq!(
+            Login::ban_kick_reason(&db_pool, "week").await,
+            AccountKickedReasons::Reason7DaysSuspended
+        );
+        create_user(&db_pool, "forever", Some(i64::MAX)).await;
+        assert_eq!(
+            Login::ban_kick_reason(&db_pool, "forever").await,
+            AccountKickedReasons::ReasonPermanentlyBanned
+        );
+    }
+
+    #[tokio::test]
+    async fn test_negative_access_level_is_rejected() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "blocked", None).await;
+        let user = user::Model::find_by_username(&db_pool, "blocked").await.unwrap();
+        let mut active_model: user::ActiveModel = user.into();
+        active_model.access_level = ActiveValue::Set(-1);
+        active_model.update(&db_pool).await.unwrap();
+        let res = lc.authenticate(&db_pool, "127.0.0.1", "blocked", "secret").await;
+        assert!(matches!(
+            res,
+            Err(PlayerLoginFailReasons::ReasonAccountSuspendedCall)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_ban_is_cleared() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "unbanned", Some(Utc::now().timestamp() - 10)).await;
+        let user = lc.authenticate(&db_pool, "127.0.0.1", "unbanned", "secret").await.unwrap();
+        assert_eq!(user.ban_duration, None);
+        assert_eq!(user.ban_ip, None);
+    }
+
+    #[tokio::test]
+    async fn test_name_rules_apply_to_new_accounts_only() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "old.timer", None).await;
+        let user = lc.authenticate(&db_pool, "127.0.0.1", "Old.Timer", "secret").await.unwrap();
+        assert_eq!(user.username, "old.timer");
+        let res = lc.authenticate(&db_pool, "127.0.0.1", "new.comer", "secret").await;
+        assert!(matches!(res, Err(PlayerLoginFailReasons::ReasonUserOrPassWrong)));
+        assert!(user::Model::find_some_by_username(&db_pool, "new.comer")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_active_ip_bans_loaded() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "banned", Some(Utc::now().timestamp() + 3600)).await;
+        lc.load_ip_bans(&db_pool).await.unwrap();
+        assert!(lc.is_ip_banned("127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_account_locked_after_wrong_passwords() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "victim", None).await;
+        let max_failures = lc.get_config().brute_force.max_account_failures;
+        for _ in 0..max_failures {
+            let res = lc.authenticate(&db_pool, "10.0.0.1", "victim", "wrong").await;
+            assert!(matches!(res, Err(PlayerLoginFailReasons::ReasonUserOrPassWrong)));
+        }
+        let res = lc.authenticate(&db_pool, "10.0.0.1", "victim", "secret").await;
+        assert!(matches!(
+            res,
+            Err(PlayerLoginFailReasons::ReasonAccessFailedTryAgainLater)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ip_banned_after_failures() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        let max_failures = lc.get_config().brute_force.max_ip_failures;
+        for i in 0..max_failures {
+            assert!(!lc.is_ip_banned("10.0.0.2"));
+            lc.on_login_failed(&db_pool, "10.0.0.2", &format!("account{i}"))
+                .await;
+        }
+        assert!(lc.is_ip_banned("10.0.0.2"));
+        assert!(!lc.is_ip_banned("10.0.0.3"));
+        // the ban survives a restart
+        let restarted = login_controller();
+        restarted.load_ip_bans(&db_pool).await.unwrap();
+        assert!(restarted.is_ip_banned("10.0.0.2"));
+    }
+
+    #[tokio::test]
+    async fn test_online_count_follows_players() {
+        let lc = login_controller();
+        let gs = GSInfo::new(
+            1,
+            false,
+            false,
+            7777,
+            true,
+            0,
+            false,
+            1,
+            0,
+            false,
+            2,
+            vec![],
+            &["127.0.0.1/32".to_string(), "127.0.0.1".to_string()],
+        )
+        .unwrap();
+        lc.game_servers.insert(1, gs);
+        lc.on_players_in_game(1, &["first".to_string(), "second".to_string()]);
+        let gs = lc.get_game_server(1).unwrap();
+        assert_eq!(gs.get_current_players(), 2);
+        assert!(matches!(gs.get_server_status(), Some(ServerStatus::Full)));
+        lc.on_player_logout("first");
+        let server_list = lc.get_server_list(Ipv4Addr::LOCALHOST);
+        assert_eq!(server_list[0].current_players, 1);
+        assert!(matches!(server_list[0].status, Some(ServerStatus::Auto)));
+    }
+
+    #[tokio::test]
+    async fn test_player_auth_needs_selected_server() {
+        let lc = login_controller();
+        let session = SessionKey::new();
+        lc.players.insert(
+            "admin".to_string(),
+            player::Info {
+                account_name: "admin".to_string(),
+                session: Some(session.clone()),
+                is_authed: true,
+                ..player::Info::default()
+            },
+        );
+        assert!(!lc.on_player_auth("admin", 1, &session));
+        assert!(lc.on_gs_selected("admin", 2));
+        assert!(!lc.on_player_auth("admin", 1, &session));
+        assert!(!lc.on_player_auth("admin", 2, &SessionKey::new()));
+        assert!(lc.on_player_auth("admin", 2, &session));
+        let player = lc.get_player("admin").unwrap();
+        assert_eq!(player.game_server, Some(2));
+        assert_eq!(player.selected_server, None);
+        assert!(!lc.on_player_auth("admin", 2, &session));
+    }
+
+    #[tokio::test]
+    async fn test_dual_box_limit() {
+        let mut cfg = LoginServer::from_string(include_str!("../test_data/test_config.yaml"));
+        let cafe = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 5));
+        cfg.dual_box.max_accounts_per_ip = 1;
+        cfg.dual_box.exemptions.insert(cafe, 2);
+        let lc = Login::new(Arc::new(cfg));
+        let player = |account: &str, ip: IpAddr| player::Info {
+            account_name: account.to_string(),
+            ip_address: Some(ip),
+            is_authed: true,
+            ..player::Info::default()
+        };
+        let home = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        lc.on_player_login(player("first", home)).await.unwrap();
+        assert!(matches!(
+            lc.on_player_login(player("second", home)).await,
+            Err(PlayerLoginFailReasons::ReasonDualBox)
+        ));
+        lc.on_player_login(player("third", cafe)).await.unwrap();
+        lc.on_player_login(player("fourth", cafe)).await.unwrap();
+        assert!(lc.on_player_login(player("fifth", cafe)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_session_expires_without_game_server() {
+        let lc = Arc::new(login_controller());
+        let session = SessionKey::new();
+        for account in ["idle", "joined"] {
+            lc.players.insert(
+                account.to_string(),
+                player::Info {
+                    account_name: account.to_string(),
+                    session: Some(session.clone()),
+                    is_authed: true,
+                    ..player::Info::default()
+                },
+            );
+            lc.on_gs_selected(account, 1);
+        }
+        assert!(lc.on_player_auth("joined", 1, &session));
+        for account in ["idle", "joined"] {
+            lc.expire_player_session(account, session.clone(), Duration::ZERO)
+                .await
+                .unwrap();
+        }
+        assert!(lc.get_player("idle").is_none());
+        assert!(lc.get_player("joined").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_chars_changed_on_server() {
+        let lc = login_controller();
+        lc.players.insert(
+            "admin".to_string(),
+            player::Info {
+                account_name: "admin".to_string(),
+                ..player::Info::default()
+            },
+        );
+        let db_pool = setup_test_db().await;
+        let user = user::Model::register(&db_pool, "admin", "hash".to_string())
+            .await
+            .unwrap();
+        let now = Utc::now().fixed_offset();
+        character::Model::create(&db_pool, new_char("Bob", user.id), vec![])
+            .await
+            .unwrap()
+            .set_delete_at(&db_pool, Some(now))
+            .await
+            .unwrap();
+        character::Model::create(&db_pool, new_char("Alice", user.id), vec![])
+            .await
+            .unwrap();
+        let chars = character::Model::find_characters_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        lc.on_chars_changed(2, &ReplyChars::new("admin".to_string(), &chars));
+        let info = lc.get_player("admin").unwrap().chars_on_servers[&2].clone();
+        assert_eq!(info.total_chars, 2);
+        assert_eq!(info.chars_to_delete, 1);
+        assert_eq!(info.char_deletion_timestamps, vec![now.timestamp()]);
+        // unknown accounts are not added
+        lc.on_chars_changed(2, &ReplyChars::new("ghost".to_string(), &chars));
+        assert!(lc.get_player("ghost").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_hash_upgraded_on_login() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller();
+        create_user(&db_pool, "veteran", None).await;
+        let user = user::Model::find_by_username(&db_pool, "veteran").await.unwrap();
+        user.set_password_hash(&db_pool, user::Model::legacy_hash("secret"))
+            .await
+            .unwrap();
+        let user = lc
+            .authenticate(&db_pool, "127.0.0.1", "veteran", "secret")
+            .await
+            .unwrap();
+        assert!(!user.is_legacy_hash());
+        assert!(user.password.starts_with("$argon2"));
+        assert!(user.verify_password("secret").await);
+    }
+}
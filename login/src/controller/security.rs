@@ -1,22 +1,240 @@
 use super::data::Login;
+use entities::entities::user;
+use entities::DBPool;
+use l2_core::ip_ban::IpBanEntry;
+use l2_core::packets::common::PlayerLoginFailReasons;
 use l2_core::traits::IpBan;
 use chrono::Utc;
+use dashmap::mapref::one::RefMut;
+use dashmap::DashMap;
+use pnet::ipnetwork::IpNetwork;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Failed login attempts of a single IP or account.
+#[derive(Debug, Clone, Default)]
+pub struct FailedLogins {
+    count: u32,
+    first_failure: i64,
+    locked_until: Option<i64>,
+}
+
+impl FailedLogins {
+    /// Registers a failure and returns how many failures happened within the window.
+    fn register(&mut self, now: i64, window: i64) -> u32 {
+        if self.count == 0 || now - self.first_failure > window {
+            self.count = 0;
+            self.first_failure = now;
+        }
+        self.count += 1;
+        self.count
+    }
+
+    fn is_locked_at(&self, now: i64) -> bool {
+        self.locked_until
+            .is_some_and(|locked_until| locked_until > now)
+    }
+
+    /// Nothing to remember anymore: the failures are out of the window and there is no lock.
+    fn is_expired(&self, now: i64, window: i64) -> bool {
+        !self.is_locked_at(now) && (self.count == 0 || now - self.first_failure > window)
+    }
+}
 
 impl Login {
+    /// Bans the IP until `expires_at` and stores the ban in the DB, so it survives a restart.
+    /// The ban is applied even if it can't be stored.
+    pub async fn ban_ip(&self, db_pool: &DBPool, ip: &str, expires_at: i64, reason: &str) {
+        let network = match IpNetwork::from_str(ip) {
+            Ok(network) => network,
+            Err(e) => {
+                error!("Can not ban invalid ip {ip}: {e}");
+                return;
+            }
+        };
+        let entry = IpBanEntry::timed(network, expires_at, Some(reason.to_string()));
+        if let Err(e) = self.ip_ban_list.ban_and_save(db_pool, entry.clone()).await {
+            error!("Can not save the ban of {ip}: {e}");
+            self.ip_ban_list.ban(entry);
+        }
+    }
+
     pub fn update_ip_ban_list(&self, ip: &str, ban_duration: i64) {
-        let _ = self.ip_ban_list.remove(ip);
-        self.ip_ban_list.insert(ip.to_string(), ban_duration);
+        match IpNetwork::from_str(ip) {
+            Ok(network) => self
+                .ip_ban_list
+                .ban(IpBanEntry::timed(network, ban_duration, None)),
+            Err(e) => error!("Can not ban invalid ip {ip}: {e}"),
+        }
+    }
+
+    /// Restores IP bans which are still active from the DB, so they survive a restart.
+    pub async fn load_ip_bans(&self, db_pool: &DBPool) -> anyhow::Result<()> {
+        let loaded = self.ip_ban_list.load_from_db(db_pool).await?;
+        let bans = user::Model::find_banned_ips(db_pool, Utc::now().timestamp()).await?;
+        info!("Loaded {} banned IPs", loaded + bans.len());
+        for (ip, ban_duration) in bans {
+            self.update_ip_ban_list(&ip, ban_duration);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the account is temporary locked because of too many wrong passwords.
+    pub fn check_account_lock(&self, account: &str) -> Result<(), PlayerLoginFailReasons> {
+        let now = Utc::now().timestamp();
+        let locked = self
+            .failed_logins_by_account
+            .get(account)
+            .and_then(|f| f.locked_until)
+            .is_some_and(|locked_until| locked_until > now);
+        if locked {
+            return Err(PlayerLoginFailReasons::ReasonAccessFailedTryAgainLater);
+        }
+        Ok(())
+    }
+
+    /// Returns failures of the key. While `max_tracked` keys are remembered and none of them
+    /// has expired, the oldest unlocked one is forgotten, so a flood of random names can't
+    /// exhaust memory nor stop new IPs from being counted.
+    fn failures_of<'a>(
+        &self,
+        failed_logins: &'a DashMap<String, FailedLogins>,
+        key: &str,
+        now: i64,
+    ) -> RefMut<'a, String, FailedLogins> {
+        let cfg = &self.config.brute_force;
+        if cfg.max_tracked > 0
+            && failed_logins.len() >= cfg.max_tracked
+            && !failed_logins.contains_key(key)
+        {
+            failed_logins.retain(|_, f| !f.is_expired(now, cfg.failure_window));
+            while failed_logins.len() >= cfg.max_tracked {
+                let oldest = failed_logins
+                    .iter()
+                    .min_by_key(|f| (f.is_locked_at(now), f.first_failure))
+                    .map(|f| f.key().clone());
+                let Some(oldest) = oldest else { break };
+                warn!("Too many failed logins are tracked, forgetting {oldest}");
+                failed_logins.remove(&oldest);
+            }
+        }
+        failed_logins.entry(key.to_string()).or_default()
+    }
+
+    /// Forgets failures and locks which have expired.
+    pub fn purge_failed_logins(&self, now: i64) {
+        let window = self.config.brute_force.failure_window;
+        self.failed_logins_by_ip
+            .retain(|_, f| !f.is_expired(now, window));
+        self.failed_logins_by_account
+            .retain(|_, f| !f.is_expired(now, window));
+    }
+
+    /// Periodically purges expired failed logins, see [`Login::purge_failed_logins`].
+    pub fn schedule_failed_logins_cleanup(self: &Arc<Self>) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.config.brute_force.cleanup_interval.max(1));
+        let controller = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                controller.purge_failed_logins(Utc::now().timestamp());
+            }
+        })
+    }
+
+    pub async fn on_login_failed(&self, db_pool: &DBPool, ip: &str, account: &str) {
+        let cfg = &self.config.brute_force;
+        let now = Utc::now().timestamp();
+        if cfg.max_ip_failures > 0 {
+            let mut failures = self.failures_of(&self.failed_logins_by_ip, ip, now);
+            if failures.register(now, cfg.failure_window) >= cfg.max_ip_failures {
+                warn!("Too many failed logins from {ip}, banning it");
+                *failures = FailedLogins::default();
+                drop(failures);
+                let expires_at = now + cfg.ip_ban_duration;
+                self.ban_ip(db_pool, ip, expires_at, "Too many failed logins")
+                    .await;
+            }
+        }
+        if cfg.max_account_failures > 0 {
+            let mut failures = self.failures_of(&self.failed_logins_by_account, account, now);
+            if failures.register(now, cfg.failure_window) >= cfg.max_account_failures {
+                warn!("Too many wrong passwords for {account}, locking the account");
+                *failures = FailedLogins {
+                    locked_until: Some(now + cfg.account_lock_duration),
+                    ..FailedLogins::default()
+                };
+            }
+        }
+    }
+
+    pub fn on_login_succeeded(&self, ip: &str, account: &str) {
+        self.failed_logins_by_ip.remove(ip);
+        self.failed_logins_by_account.remove(account);
+    }
+
+    pub fn watch_ip_ban_file(&self) -> Option<JoinHandle<()>> {
+        self.ip_ban_list.watch_file()
     }
 }
 impl IpBan for Login {
     fn is_ip_banned(&self, ip: &str) -> bool {
-        if let Some(res) = self.ip_ban_list.get(ip) {
-            let now = Utc::now().timestamp();
-            if now < *res {
-                return true;
-            }
-            self.ip_ban_list.remove(ip);
-        }
-        false
+        self.ip_ban_list.is_banned(ip)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::config::login::LoginServer;
+    use l2_core::tests::setup_test_db;
+    use l2_core::traits::ServerConfig;
+
+    fn login_controller(max_tracked: usize) -> Login {
+        let mut cfg = LoginServer::from_string(include_str!("../test_data/test_config.yaml"));
+        cfg.brute_force.max_tracked = max_tracked;
+        cfg.brute_force.max_account_failures = 2;
+        Login::new(Arc::new(cfg))
+    }
+
+    #[tokio::test]
+    async fn test_failed_logins_are_limited() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller(2);
+        lc.on_login_failed(&db_pool, "10.0.0.1", "alice").await;
+        lc.on_login_failed(&db_pool, "10.0.0.1", "alice").await;
+        assert!(lc.check_account_lock("alice").is_err());
+        lc.on_login_failed(&db_pool, "10.0.0.2", "bob").await;
+        lc.on_login_failed(&db_pool, "10.0.0.3", "carol").await;
+        assert_eq!(lc.failed_logins_by_ip.len(), 2);
+        assert!(lc.failed_logins_by_ip.contains_key("10.0.0.3"));
+        // the oldest unlocked account is forgotten, the lock stays
+        assert_eq!(lc.failed_logins_by_account.len(), 2);
+        assert!(!lc.failed_logins_by_account.contains_key("bob"));
+        assert!(lc.check_account_lock("alice").is_err());
+        lc.on_login_failed(&db_pool, "10.0.0.3", "carol").await;
+        assert!(lc.check_account_lock("carol").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_purge_failed_logins() {
+        let db_pool = setup_test_db().await;
+        let lc = login_controller(0);
+        lc.on_login_failed(&db_pool, "10.0.0.1", "alice").await;
+        lc.on_login_failed(&db_pool, "10.0.0.2", "bob").await;
+        lc.on_login_failed(&db_pool, "10.0.0.2", "bob").await;
+        let cfg = &lc.config.brute_force;
+        let now = Utc::now().timestamp();
+        // bob is locked longer than the failure window lasts
+        lc.purge_failed_logins(now + cfg.failure_window + 1);
+        assert!(lc.failed_logins_by_ip.is_empty());
+        assert_eq!(lc.failed_logins_by_account.len(), 1);
+        assert!(lc.failed_logins_by_account.contains_key("bob"));
+        lc.purge_failed_logins(now + cfg.account_lock_duration + 1);
+        assert!(lc.failed_logins_by_account.is_empty());
     }
 }
@@ -1,5 +1,5 @@
 use l2_core::dto::ServerHost;
-use l2_core::packets::common::ServerType;
+use l2_core::packets::common::{ServerStatus, ServerType};
 use anyhow::bail;
 use num::BigInt;
 use pnet::ipnetwork::Ipv4Network;
@@ -20,6 +20,7 @@ pub struct GSInfo {
     age_limit: u8,
     show_brackets: bool,
     max_players: u32,
+    current_players: u32,
     hex_id: Vec<u8>,
     hosts: Vec<ServerHost>,
 }
@@ -58,6 +59,7 @@ impl GSInfo {
             age_limit,
             show_brackets,
             max_players,
+            current_players: 0,
             hex_id,
             hosts: validated_hosts,
         })
@@ -114,6 +116,29 @@ impl GSInfo {
     pub fn set_max_players(&mut self, max_players: u32) {
         self.max_players = max_players;
     }
+    pub fn get_current_players(&self) -> i32 {
+        self.current_players as i32
+    }
+    pub fn set_current_players(&mut self, current_players: u32) {
+        self.current_players = current_players;
+    }
+    pub fn is_full(&self) -> bool {
+        self.max_players > 0 && self.current_players >= self.max_players
+    }
+    ///
+    /// Status shown to the clients: an online server which reached `max_players`
+    /// is reported as `Full`, `Down` and `GmOnly` are kept as is.
+    ///
+    pub fn get_server_status(&self) -> Option<ServerStatus> {
+        match ServerStatus::try_from(self.get_status()).ok() {
+            Some(ServerStatus::Auto | ServerStatus::Good | ServerStatus::Normal)
+                if self.is_full() =>
+            {
+                Some(ServerStatus::Full)
+            }
+            status => status,
+        }
+    }
     pub fn set_age_limit(&mut self, age_limit: u8) {
         self.age_limit = age_limit;
     }
@@ -173,6 +198,41 @@ impl GSInfo {
 #[cfg(test)]
 mod test {
     use crate::dto::game_server::GSInfo;
+    use l2_core::packets::common::ServerStatus;
+
+    fn gs_info(status: ServerStatus, max_players: u32) -> GSInfo {
+        GSInfo::new(
+            1,
+            false,
+            false,
+            7777,
+            true,
+            status as i32,
+            false,
+            1,
+            0,
+            false,
+            max_players,
+            vec![],
+            &["127.0.0.1/32".to_string(), "127.0.0.1".to_string()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_server_becomes_full() {
+        let mut gs = gs_info(ServerStatus::Auto, 2);
+        gs.set_current_players(1);
+        assert!(matches!(gs.get_server_status(), Some(ServerStatus::Auto)));
+        gs.set_current_players(2);
+        assert!(matches!(gs.get_server_status(), Some(ServerStatus::Full)));
+    }
+    #[test]
+    fn test_down_server_is_never_full() {
+        let mut gs = gs_info(ServerStatus::Down, 1);
+        gs.set_current_players(5);
+        assert!(matches!(gs.get_server_status(), Some(ServerStatus::Down)));
+    }
     #[test]
     fn test_validated_hosts_error() {
         let hosts = vec!["127.0.0.1"];
@@ -1,3 +1,5 @@
+use crate::client_thread::ClientSender;
+use l2_core::packets::gs_2_ls::ReplyChars;
 use l2_core::session::SessionKey;
 use std::collections::HashMap;
 use std::net::IpAddr;
@@ -8,6 +10,16 @@ pub struct GSCharsInfo {
     pub chars_to_delete: u8,
     pub char_deletion_timestamps: Vec<i64>,
 }
+
+impl From<&ReplyChars> for GSCharsInfo {
+    fn from(reply: &ReplyChars) -> Self {
+        Self {
+            total_chars: reply.chars,
+            chars_to_delete: reply.delete_chars_len,
+            char_deletion_timestamps: reply.char_deletion_timestamps.clone(),
+        }
+    }
+}
 #[derive(Debug, Clone, Default)]
 pub struct Info {
     pub session: Option<SessionKey>,
@@ -18,6 +30,12 @@ pub struct Info {
     pub ip_address: Option<IpAddr>,
     pub chars_on_servers: HashMap<u8, GSCharsInfo>,
     pub game_server: Option<u8>,
+    /// The server chosen in the server list, the player is expected to appear there.
+    pub selected_server: Option<u8>,
+    pub last_server: u8,
+    pub access_level: i32,
+    /// Set while the client is connected to the login server.
+    pub login_client: Option<ClientSender>,
 }
 
 impl Info {
@@ -34,6 +34,11 @@ pub fn main() {
         .init();
     LoginServer::bootstrap("config/login.yaml", |cfg, db_pool| async move {
         let lc = Arc::new(Login::new(cfg.clone()));
+        if let Err(e) = lc.load_ip_bans(&db_pool).await {
+            error!("Failed to load banned IPs: {e}");
+        }
+        let _ban_file_watcher = lc.watch_ip_ban_file();
+        let _failed_logins_cleanup = lc.schedule_failed_logins_cleanup();
         let mut clients_handle =
             LoginServer::listener_loop::<ClientHandler>(cfg.clone(), lc.clone(), db_pool.clone());
 
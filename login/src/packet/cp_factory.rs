@@ -3,7 +3,8 @@ use l2_core::packets::common::ReadablePacket;
 use l2_core::crypt::rsa::ScrambledRSAKeyPair;
 use crate::client_thread::ClientHandler;
 use crate::packet::from_client::{
-    RequestAuthGG, RequestAuthLogin, RequestGSLogin, RequestServerList,
+    RequestAuthGG, RequestAuthLogin, RequestCmdLogin, RequestGSLogin, RequestPiAgreement,
+    RequestPiAgreementCheck, RequestServerList,
 };
 use crate::packet::HandleablePacket;
 
@@ -32,11 +33,17 @@ pub fn build_client_packet(
             Some(Box::new(RequestAuthLogin::read(&decrypted).unwrap()))
         }
         0x07 => Some(Box::new(RequestAuthGG::read(packet_body).unwrap())),
-        // 0x0B => Some(), //cmd login
+        0x0B => {
+            let start = RequestCmdLogin::RSA_BLOCK_OFFSET;
+            let end = start + RequestCmdLogin::RSA_BLOCK_SIZE;
+            let raw = packet_body.get(start..end)?;
+            let decrypted = key_pair.decrypt_data(raw).ok()?;
+            Some(Box::new(RequestCmdLogin::read(&decrypted)?))
+        }
         0x02 => Some(Box::new(RequestGSLogin::read(packet_body).unwrap())),
         0x05 => Some(Box::new(RequestServerList::read(packet_body).unwrap())),
-        // 0x0E => Some(LoginClientOpcodes::RequestPiAgreementCheck),
-        // 0x0F => Some(LoginClientOpcodes::RequestPiAgreement),
+        0x0E => Some(Box::new(RequestPiAgreementCheck::read(packet_body)?)),
+        0x0F => Some(Box::new(RequestPiAgreement::read(packet_body)?)),
         _ => {
             error!("Unknown Client packet ID:0x{:02X}", data[0]);
             None
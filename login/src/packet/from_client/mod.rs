@@ -1,9 +1,14 @@
 mod req_auth_gg;
 mod req_auth_login;
+mod req_cmd_login;
+mod req_pi_agreement;
+mod req_pi_agreement_check;
 mod req_server_list;
 mod request_gs_login;
 
 pub use self::{
     req_auth_gg::RequestAuthGG, req_auth_login::RequestAuthLogin,
-    req_server_list::RequestServerList, request_gs_login::RequestGSLogin,
+    req_cmd_login::RequestCmdLogin, req_pi_agreement::RequestPiAgreement,
+    req_pi_agreement_check::RequestPiAgreementCheck, req_server_list::RequestServerList,
+    request_gs_login::RequestGSLogin,
 };
@@ -1,16 +1,13 @@
 use crate::client_thread::ClientHandler;
-use crate::dto::player;
-use crate::packet::to_client::LoginOk;
-use crate::packet::to_client::ServerList;
+use crate::controller::Login;
 use crate::packet::HandleablePacket;
 use async_trait::async_trait;
 use entities::entities::user;
-use l2_core::hash_password;
+use crate::packet::to_client::AccountKicked;
 use l2_core::packets::common::{PlayerLoginFail, PlayerLoginFailReasons, ReadablePacket};
 use l2_core::packets::error::PacketRun;
 use l2_core::str::Trim;
 use l2_core::traits::handlers::{PacketHandler, PacketSender};
-use sea_orm::{ActiveModelTrait, ActiveValue};
 
 #[derive(Clone, Debug)]
 #[allow(unused)]
@@ -37,13 +34,15 @@ impl ReadablePacket for RequestAuthLogin {
                 .trim_all()
                 .to_string();
         } else {
-            username = String::from_utf8_lossy(&body[0x5E..0x5E + 14]).to_string();
+            username = String::from_utf8_lossy(&body[0x5E..0x5E + 14])
+                .trim_all()
+                .to_string();
             password = String::from_utf8_lossy(&body[0x6C..0x6C + 16])
                 .trim_all()
                 .to_string();
         }
         Some(Self {
-            username,
+            username: user::Model::normalize_username(&username),
             password,
             is_new_auth
         })
@@ -54,53 +53,29 @@ impl ReadablePacket for RequestAuthLogin {
 impl HandleablePacket for RequestAuthLogin {
     type HandlerType = ClientHandler;
     async fn handle(&self, ch: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        let auto_registration = ch.get_controller().get_config().auto_registration;
-        let show_license = ch.get_controller().get_config().client.show_licence;
+        let lc = ch.get_controller().clone();
+        let ip = ch.ip.to_string();
         let pool = ch.get_db_pool_mut();
-        let user_option = user::Model::find_some_by_username(pool, &self.username).await?;
-
-        if let Some(user) = user_option {
-            if !user.verify_password(&self.password).await {
-                ch.send_packet(Box::new(PlayerLoginFail::new(
-                    PlayerLoginFailReasons::ReasonUserOrPassWrong,
-                )))
+        let user = match lc
+            .authenticate(pool, &ip, &self.username, &self.password)
+            .await
+        {
+            Ok(user) => user,
+            Err(PlayerLoginFailReasons::ReasonAccountSuspendedCall) => {
+                let reason = Login::ban_kick_reason(pool, &self.username).await;
+                ch.send_packet(Box::new(AccountKicked::new(reason)))
                 .await?;
+                return Err(PacketRun {
+                    msg: Some(format!("Banned account tried to login: {}", self.username)),
+                });
+            }
+            Err(reason) => {
+                ch.send_packet(Box::new(PlayerLoginFail::new(reason))).await?;
                 return Err(PacketRun {
                     msg: Some(format!("Login Fail, tried user: {}", self.username)),
                 });
             }
-        } else if auto_registration {
-            let password_hash = hash_password(&self.password).await?;
-            let user_record = user::ActiveModel {
-                id: ActiveValue::NotSet,
-                username: ActiveValue::Set(self.username.clone()),
-                password: ActiveValue::Set(password_hash),
-                access_level: ActiveValue::Set(0),
-                ban_duration: ActiveValue::NotSet,
-                ban_ip: ActiveValue::NotSet,
-            };
-            user_record.save(pool).await?;
-        }
-        ch.account_name = Some(self.username.clone());
-        let player_info = player::Info {
-            is_authed: true,
-            session: Some(ch.get_session_key().clone()),
-            account_name: self.username.clone(),
-            ..Default::default()
         };
-        let lc = ch.get_controller();
-        if let Err(err) = lc.on_player_login(player_info).await {
-            let err_msg = format!("Player login failed: {err:?}");
-            ch.send_packet(Box::new(PlayerLoginFail::new(err))).await?;
-            return Err(PacketRun { msg: Some(err_msg) });
-        }
-        if show_license {
-            ch.send_packet(Box::new(LoginOk::new(ch.get_session_key())))
-                .await?;
-        } else {
-            let s_list = ServerList::new(ch, &self.username);
-            ch.send_packet(Box::new(s_list)).await?;
-        }
-        Ok(())
+        ch.complete_login(&user).await
     }
 }
@@ -0,0 +1,99 @@
+use crate::client_thread::ClientHandler;
+use crate::controller::Login;
+use crate::packet::to_client::{AccountKicked, LoginOptFail};
+use crate::packet::HandleablePacket;
+use async_trait::async_trait;
+use entities::entities::user;
+use l2_core::packets::common::{PlayerLoginFail, PlayerLoginFailReasons, ReadablePacket};
+use l2_core::packets::error::PacketRun;
+use l2_core::str::Trim;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+
+/// Login with credentials passed to the client on the command line.
+/// Unlike `RequestAuthLogin` it carries a single RSA block, the factory decrypts it
+/// and passes here only the decrypted bytes.
+#[derive(Clone, Debug)]
+pub struct RequestCmdLogin {
+    pub username: String,
+    pub password: String,
+}
+
+impl RequestCmdLogin {
+    pub const RSA_BLOCK_OFFSET: usize = 4;
+    pub const RSA_BLOCK_SIZE: usize = 128;
+}
+
+impl ReadablePacket for RequestCmdLogin {
+    fn read(data: &[u8]) -> Option<Self> {
+        if data.len() < 0x60 + 16 {
+            return None;
+        }
+        let username = user::Model::normalize_username(
+            String::from_utf8_lossy(&data[0x40..0x40 + 14]).trim_all(),
+        );
+        let password = String::from_utf8_lossy(&data[0x60..0x60 + 16])
+            .trim_all()
+            .to_string();
+        Some(Self { username, password })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestCmdLogin {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, ch: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let lc = ch.get_controller().clone();
+        if !lc.get_config().client.enable_cmdline_login {
+            ch.send_packet(Box::new(LoginOptFail::new())).await?;
+            return Err(PacketRun {
+                msg: Some(format!(
+                    "Command line login is disabled, tried user: {}",
+                    self.username
+                )),
+            });
+        }
+        let ip = ch.ip.to_string();
+        let pool = ch.get_db_pool_mut();
+        let user = match lc
+            .authenticate(pool, &ip, &self.username, &self.password)
+            .await
+        {
+            Ok(user) => user,
+            Err(reason) => {
+                if matches!(reason, PlayerLoginFailReasons::ReasonUserOrPassWrong) {
+                    ch.send_packet(Box::new(LoginOptFail::new())).await?;
+                } else if matches!(reason, PlayerLoginFailReasons::ReasonAccountSuspendedCall) {
+                    let reason = Login::ban_kick_reason(pool, &self.username).await;
+                    ch.send_packet(Box::new(AccountKicked::new(reason)))
+                    .await?;
+                } else {
+                    ch.send_packet(Box::new(PlayerLoginFail::new(reason))).await?;
+                }
+                return Err(PacketRun {
+                    msg: Some(format!("Cmd login fail, tried user: {}", self.username)),
+                });
+            }
+        };
+        ch.complete_login(&user).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_credentials() {
+        let mut data = vec![0u8; 128];
+        data[0x40..0x40 + 5].copy_from_slice(b"Admin");
+        data[0x60..0x60 + 6].copy_from_slice(b"secret");
+        let packet = RequestCmdLogin::read(&data).unwrap();
+        assert_eq!(packet.username, "admin");
+        assert_eq!(packet.password, "secret");
+    }
+
+    #[test]
+    fn test_read_too_short() {
+        assert!(RequestCmdLogin::read(&[0u8; 64]).is_none());
+    }
+}
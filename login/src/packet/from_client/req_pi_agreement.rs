@@ -0,0 +1,71 @@
+use crate::client_thread::ClientHandler;
+use crate::packet::to_client::PIAgreementAck;
+use crate::packet::HandleablePacket;
+use async_trait::async_trait;
+use entities::entities::user;
+use l2_core::packets::common::{PlayerLoginFail, PlayerLoginFailReasons, ReadablePacket};
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use sea_orm::{ActiveModelTrait, ActiveValue};
+
+/// The answer of the player to the personal information agreement, status 1 means accepted.
+#[derive(Clone, Debug)]
+pub struct RequestPiAgreement {
+    pub account_id: i32,
+    pub status: u8,
+}
+
+impl ReadablePacket for RequestPiAgreement {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        if buffer.get_remaining_length() < 5 {
+            return None;
+        }
+        Some(Self {
+            account_id: buffer.read_i32(),
+            status: buffer.read_byte(),
+        })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestPiAgreement {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, ch: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let Some(acc_name) = ch.account_name.clone() else {
+            ch.send_packet(Box::new(PlayerLoginFail::new(
+                PlayerLoginFailReasons::ReasonAccessFailed,
+            )))
+            .await?;
+            return Err(PacketRun {
+                msg: Some("PI agreement answered before login".to_string()),
+            });
+        };
+        if self.status == 1 {
+            let db_pool = ch.get_db_pool_mut();
+            let user_model = user::Model::find_by_username(db_pool, &acc_name).await?;
+            if !user_model.pi_agreement {
+                let mut active_model: user::ActiveModel = user_model.into();
+                active_model.pi_agreement = ActiveValue::Set(true);
+                active_model.save(db_pool).await?;
+            }
+        }
+        ch.send_packet(Box::new(PIAgreementAck::new(self.account_id, self.status)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let packet = RequestPiAgreement::read(&[0x2A, 0, 0, 0, 1]).unwrap();
+        assert_eq!(packet.account_id, 42);
+        assert_eq!(packet.status, 1);
+        assert!(RequestPiAgreement::read(&[0x2A, 0, 0, 0]).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::client_thread::ClientHandler;
+use crate::packet::to_client::PIAgreementCheck;
+use crate::packet::HandleablePacket;
+use async_trait::async_trait;
+use entities::entities::user;
+use l2_core::packets::common::{PlayerLoginFail, PlayerLoginFailReasons, ReadablePacket};
+use l2_core::packets::error::PacketRun;
+use l2_core::packets::read::ReadablePacketBuffer;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+
+#[derive(Clone, Debug)]
+pub struct RequestPiAgreementCheck {
+    pub account_id: i32,
+}
+
+impl ReadablePacket for RequestPiAgreementCheck {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data.to_vec());
+        if buffer.get_remaining_length() < 4 {
+            return None;
+        }
+        Some(Self {
+            account_id: buffer.read_i32(),
+        })
+    }
+}
+
+#[async_trait]
+impl HandleablePacket for RequestPiAgreementCheck {
+    type HandlerType = ClientHandler;
+    async fn handle(&self, ch: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let Some(acc_name) = ch.account_name.clone() else {
+            ch.send_packet(Box::new(PlayerLoginFail::new(
+                PlayerLoginFailReasons::ReasonAccessFailed,
+            )))
+            .await?;
+            return Err(PacketRun {
+                msg: Some("PI agreement check requested before login".to_string()),
+            });
+        };
+        let show_pi_agreement = ch.get_controller().get_config().client.show_pi_agreement;
+        let db_pool = ch.get_db_pool_mut();
+        let user_model = user::Model::find_by_username(db_pool, &acc_name).await?;
+        let status = u8::from(show_pi_agreement && !user_model.pi_agreement);
+        ch.send_packet(Box::new(PIAgreementCheck::new(self.account_id, status)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let packet = RequestPiAgreementCheck::read(&[0x2A, 0, 0, 0]).unwrap();
+        assert_eq!(packet.account_id, 42);
+        assert!(RequestPiAgreementCheck::read(&[0x2A]).is_none());
+    }
+}
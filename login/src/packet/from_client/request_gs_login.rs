@@ -1,11 +1,11 @@
 use crate::client_thread::ClientHandler;
-use crate::packet::to_client::PlayOk;
+use crate::packet::to_client::{PlayFail, PlayOk};
 use crate::packet::HandleablePacket;
 use async_trait::async_trait;
-use l2_core::packets::common::ReadablePacket;
+use l2_core::packets::common::{PlayFailReasons, ReadablePacket};
 use l2_core::packets::error::PacketRun;
 use l2_core::packets::read::ReadablePacketBuffer;
-use l2_core::traits::handlers::PacketSender;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
 
 #[derive(Clone, Debug)]
 #[allow(unused)]
@@ -30,9 +30,31 @@ impl ReadablePacket for RequestGSLogin {
 impl HandleablePacket for RequestGSLogin {
     type HandlerType = ClientHandler;
     async fn handle(&self, ch: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        ch.check_session(self.s_key_1, self.s_key_2)?;
+        if let Err(e) = ch.check_session(self.s_key_1, self.s_key_2) {
+            ch.send_packet(Box::new(PlayFail::new(
+                PlayFailReasons::ReasonAccessFailedTryAgainLater,
+            )))
+            .await?;
+            return Err(e.into());
+        }
+        let account_name = ch.account_name.clone().unwrap_or_default();
+        if let Err(reason) = ch
+            .get_controller()
+            .check_gs_login(&account_name, self.server_id)
+        {
+            ch.send_packet(Box::new(PlayFail::new(reason))).await?;
+            return Err(PacketRun {
+                msg: Some(format!(
+                    "Account {account_name} can't enter game server {}",
+                    self.server_id
+                )),
+            });
+        }
+        ch.get_controller()
+            .on_gs_selected(&account_name, self.server_id);
         ch.send_packet(Box::new(PlayOk::new(ch.get_session_key())?))
             .await?;
+        ch.set_play_ok_sent();
         Ok(())
     }
 }
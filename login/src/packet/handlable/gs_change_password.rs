@@ -1,13 +1,121 @@
 use crate::gs_thread::GSHandler;
 use async_trait::async_trait;
+use entities::entities::user;
 use l2_core::packets::error::PacketRun;
 use l2_core::packets::gs_2_ls::ChangePassword;
+use l2_core::packets::ls_2_gs;
+use l2_core::traits::accounts::Credentials;
+use l2_core::traits::handlers::{PacketHandler, PacketSender};
+use tracing::{error, info, instrument};
 use crate::packet::HandleablePacket;
 
+/// The client sends only 16 bytes for the password, so longer passwords can't be used to log in.
+const PASSWORD_LEN: std::ops::RangeInclusive<usize> = 3..=16;
+
 #[async_trait]
 impl HandleablePacket for ChangePassword {
     type HandlerType = GSHandler;
-    async fn handle(&self, _: &mut Self::HandlerType) -> Result<(), PacketRun> {
-        todo!()
+
+    #[instrument(skip(self, gs), fields(account = %self.account))]
+    async fn handle(&self, gs: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let lc = gs.get_controller().clone();
+        let db_pool = gs.get_db_pool_mut().clone();
+        let message = if PASSWORD_LEN.contains(&self.new_password.chars().count()) {
+            match user::Model::find_some_by_username(&db_pool, &self.account).await? {
+                Some(user) => match lc
+                    .change_password(&db_pool, user, &self.current_password, &self.new_password)
+                    .await
+                {
+                    Ok(Credentials::Valid) => {
+                        info!("[change password] OK");
+                        ls_2_gs::ChangePassword::SUCCESS
+                    }
+                    Ok(Credentials::WrongPassword) => ls_2_gs::ChangePassword::WRONG_PASSWORD,
+                    Ok(Credentials::UnknownAccount) => ls_2_gs::ChangePassword::FAILED,
+                    Err(e) => {
+                        // e.g. the account backend is down, the game server stays connected
+                        error!("[change password] Failed: {e}");
+                        ls_2_gs::ChangePassword::FAILED
+                    }
+                },
+                None => ls_2_gs::ChangePassword::FAILED,
+            }
+        } else {
+            ls_2_gs::ChangePassword::INVALID_PASSWORD
+        };
+        gs.send_packet(Box::new(ls_2_gs::ChangePassword::new(
+            &self.char_name,
+            message,
+        )?))
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::controller::Login;
+    use l2_core::config::login::LoginServer;
+    use l2_core::crypt::login::Encryption;
+    use l2_core::hash_password;
+    use l2_core::packets::common::ReadablePacket;
+    use l2_core::tests::{setup_test_db, DBPool};
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Game server handler of the login server and the socket of the game server.
+    async fn gs_handler(db_pool: DBPool) -> (GSHandler, TcpStream) {
+        let cfg = LoginServer::from_string(include_str!("../../test_data/test_config.yaml"));
+        let lc = Arc::new(Login::new(Arc::new(cfg)));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (game_server, _) = listener.accept().await.unwrap();
+        (GSHandler::new(stream, db_pool, lc), game_server)
+    }
+
+    async fn read_reply(gs: &GSHandler, game_server: &mut TcpStream) -> ls_2_gs::ChangePassword {
+        let mut size = [0; 2];
+        game_server.read_exact(&mut size).await.unwrap();
+        let mut body = vec![0; usize::from(u16::from_le_bytes(size)) - 2];
+        game_server.read_exact(&mut body).await.unwrap();
+        let cfg = gs.get_controller().get_config();
+        let blowfish = Encryption::from_u8_key(cfg.blowfish_key.as_bytes());
+        blowfish.decrypt(&mut body).unwrap();
+        ls_2_gs::ChangePassword::read(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let db_pool = setup_test_db().await;
+        let password_hash = hash_password("secret").await.unwrap();
+        user::Model::register(&db_pool, "admin", password_hash)
+            .await
+            .unwrap();
+        let (mut gs, mut game_server) = gs_handler(db_pool.clone()).await;
+        let request =
+            |current: &str, new: &str| ChangePassword::new("admin", "hero", current, new).unwrap();
+
+        request("wrong", "newpass").handle(&mut gs).await.unwrap();
+        let reply = read_reply(&gs, &mut game_server).await;
+        assert_eq!(reply.char_name, "hero");
+        assert_eq!(reply.message, ls_2_gs::ChangePassword::WRONG_PASSWORD);
+
+        request("secret", "ab").handle(&mut gs).await.unwrap();
+        let reply = read_reply(&gs, &mut game_server).await;
+        assert_eq!(reply.message, ls_2_gs::ChangePassword::INVALID_PASSWORD);
+        let user = user::Model::find_by_username(&db_pool, "admin").await.unwrap();
+        assert!(user.verify_password("secret").await);
+
+        request("secret", "newpass").handle(&mut gs).await.unwrap();
+        let reply = read_reply(&gs, &mut game_server).await;
+        assert_eq!(reply.message, ls_2_gs::ChangePassword::SUCCESS);
+        let user = user::Model::find_by_username(&db_pool, "admin").await.unwrap();
+        assert!(user.verify_password("newpass").await);
+        assert!(!user.verify_password("secret").await);
     }
 }
@@ -11,6 +11,10 @@ impl HandleablePacket for ReplyChars {
     type HandlerType = GSHandler;
     async fn handle(&self, gs: &mut Self::HandlerType) -> Result<(), PacketRun> {
         let controller = gs.get_controller();
+        if let Some(gs_id) = gs.server_id {
+            // replies to RequestChars are picked up by the login flow, the rest are updates
+            controller.on_chars_changed(gs_id, self);
+        }
         controller.message_broker.respond_to_message(
             gs.server_id,
             &self.account_name,
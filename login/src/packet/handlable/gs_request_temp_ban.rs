@@ -25,8 +25,10 @@ impl HandleablePacket for RequestTempBan {
         active_record.ban_ip = ActiveValue::Set(Some(self.ip.clone()));
         active_record.save(db_pool).await?;
         info!("[Account banned] OK: {:?}", self.account);
-        let lc = gs.get_controller();
-        lc.update_ip_ban_list(&self.ip, self.ban_duration);
+        let lc = gs.get_controller().clone();
+        let reason = format!("Temporary ban of {}", self.account);
+        lc.ban_ip(gs.get_db_pool_mut(), &self.ip, self.ban_duration, &reason)
+            .await;
         lc.remove_player(&self.account);
         Ok(())
     }
@@ -1,9 +1,12 @@
 use async_trait::async_trait;
+use chrono::Utc;
+use entities::entities::{account_access_log, user};
+use tracing::error;
 use l2_core::{
     packets::{
         error::PacketRun,
         gs_2_ls::PlayerAuthRequest,
-        ls_2_gs::{KickPlayer, PlayerAuthResponse},
+        ls_2_gs::PlayerAuthResponse,
     },
     traits::handlers::PacketHandler,
 };
@@ -18,18 +21,11 @@ impl HandleablePacket for PlayerAuthRequest {
     type HandlerType = GSHandler;
     async fn handle(&self, gs: &mut Self::HandlerType) -> Result<(), PacketRun> {
         let lc = gs.get_controller();
-        let show_license = lc.get_config().client.show_licence;
-        let operation_ok = lc.with_player(&self.account_name, |pl| {
-            if let Some(session) = &pl.session {
-                if session.equals(&self.session, show_license) {
-                    pl.game_server = gs.server_id;
-                    return true;
-                }
-            }
-            false // operation wasn't successful
-        });
+        let operation_ok = gs
+            .server_id
+            .is_some_and(|gs_id| lc.on_player_auth(&self.account_name, gs_id, &self.session));
         if !operation_ok {
-            gs.send_packet(Box::new(KickPlayer::new(&self.account_name)))
+            gs.send_packet(Box::new(PlayerAuthResponse::new(&self.account_name, false)))
                 .await?;
             return Err(PacketRun {
                 msg: Some(format!(
@@ -38,6 +34,29 @@ impl HandleablePacket for PlayerAuthRequest {
                 )),
             });
         }
+        if let Some(server_id) = gs.server_id {
+            lc.update_gs_online_count(server_id);
+            let ip = lc
+                .get_player(&self.account_name)
+                .and_then(|pl| pl.ip_address)
+                .map(|ip| ip.to_string());
+            let db_pool = gs.get_db_pool_mut();
+            if let Err(e) = user::Model::set_last_server(db_pool, &self.account_name, server_id).await {
+                error!("Can't save last server of {}: {e}", self.account_name);
+            }
+            let now = Utc::now().timestamp();
+            if let Err(e) = account_access_log::Model::record_login(
+                db_pool,
+                &self.account_name,
+                ip,
+                Some(server_id),
+                now,
+            )
+            .await
+            {
+                error!("Can't record login of {}: {e}", self.account_name);
+            }
+        }
         gs.send_packet(Box::new(PlayerAuthResponse::new(&self.account_name, true)))
             .await?;
         Ok(())
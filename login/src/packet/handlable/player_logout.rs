@@ -1,4 +1,7 @@
 use async_trait::async_trait;
+use chrono::Utc;
+use entities::entities::account_access_log;
+use tracing::error;
 
 use crate::gs_thread::GSHandler;
 use crate::packet::HandleablePacket;
@@ -11,6 +14,12 @@ impl HandleablePacket for PlayerLogout {
     async fn handle(&self, gs: &mut Self::HandlerType) -> Result<(), PacketRun> {
         let lc = gs.get_controller();
         lc.on_player_logout(&self.acc);
+        let now = Utc::now().timestamp();
+        if let Err(e) =
+            account_access_log::Model::record_logout(gs.get_db_pool_mut(), &self.acc, now).await
+        {
+            error!("Can't record logout of {}: {e}", self.acc);
+        }
         Ok(())
     }
 }
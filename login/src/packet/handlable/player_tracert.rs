@@ -1,4 +1,8 @@
 use async_trait::async_trait;
+use chrono::Utc;
+use entities::entities::account_access_log;
+use l2_core::traits::handlers::PacketHandler;
+use tracing::{error, info};
 
 use crate::gs_thread::GSHandler;
 use l2_core::packets::{error::PacketRun, gs_2_ls::PlayerTracert};
@@ -7,7 +11,24 @@ use crate::packet::HandleablePacket;
 #[async_trait]
 impl HandleablePacket for PlayerTracert {
     type HandlerType = GSHandler;
-    async fn handle(&self, _: &mut Self::HandlerType) -> Result<(), PacketRun> {
+    async fn handle(&self, gs: &mut Self::HandlerType) -> Result<(), PacketRun> {
+        let server_id = gs.server_id;
+        info!(
+            "Tracert of {}: {} {} {} {} {}",
+            self.account, self.pc_ip, self.hop1, self.hop2, self.hop3, self.hop4
+        );
+        if let Err(e) = account_access_log::Model::record_tracert(
+            gs.get_db_pool_mut(),
+            &self.account,
+            server_id,
+            &self.pc_ip,
+            [&self.hop1, &self.hop2, &self.hop3, &self.hop4],
+            Utc::now().timestamp(),
+        )
+        .await
+        {
+            error!("Can't save tracert of {}: {e}", self.account);
+        }
         Ok(())
     }
 }
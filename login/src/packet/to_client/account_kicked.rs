@@ -0,0 +1,41 @@
+use l2_core::packets::common::{AccountKickedReasons, LoginServerOpcodes, SendablePacket};
+use l2_core::packets::write::SendablePacketBuffer;
+
+#[derive(Debug, Clone)]
+pub struct AccountKicked {
+    pub buffer: SendablePacketBuffer,
+    pub reason: AccountKickedReasons,
+}
+
+impl AccountKicked {
+    pub fn new(reason: AccountKickedReasons) -> AccountKicked {
+        let mut inst = AccountKicked {
+            buffer: SendablePacketBuffer::new(),
+            reason,
+        };
+        let _ = inst.write_all();
+        inst
+    }
+    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+        self.buffer.write(LoginServerOpcodes::AccountKicked as u8)?;
+        self.buffer.write_i32(self.reason as i32)?;
+        Ok(())
+    }
+}
+
+impl SendablePacket for AccountKicked {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_account_kicked() {
+        let mut packet = AccountKicked::new(AccountKickedReasons::ReasonPermanentlyBanned);
+        assert_eq!(packet.buffer.get_data(), [7, 0, 0x02, 0x20, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,27 @@
+use l2_core::packets::common::{LoginServerOpcodes, SendablePacket};
+use l2_core::packets::write::SendablePacketBuffer;
+
+#[derive(Debug, Clone)]
+pub struct LoginOptFail {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl LoginOptFail {
+    pub fn new() -> LoginOptFail {
+        let mut inst = LoginOptFail {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let _ = inst.write_all();
+        inst
+    }
+    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+        self.buffer.write(LoginServerOpcodes::LoginOptFail as u8)?;
+        Ok(())
+    }
+}
+
+impl SendablePacket for LoginOptFail {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
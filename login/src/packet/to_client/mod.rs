@@ -1,9 +1,17 @@
+mod account_kicked;
 mod auth_gg;
 mod init;
 mod login_ok;
+mod login_opt_fail;
+mod pi_agreement_ack;
+mod pi_agreement_check;
+mod play_fail;
 mod play_ok;
 mod server_list;
 
 pub use self::{
-    auth_gg::AuthGG, init::Init, login_ok::LoginOk, play_ok::PlayOk, server_list::ServerList,
+    account_kicked::AccountKicked, auth_gg::AuthGG, init::Init, login_ok::LoginOk, login_opt_fail::LoginOptFail,
+    pi_agreement_ack::PIAgreementAck, pi_agreement_check::PIAgreementCheck, play_fail::PlayFail,
+    play_ok::PlayOk,
+    server_list::ServerList,
 };
@@ -0,0 +1,34 @@
+use l2_core::packets::common::{LoginServerOpcodes, SendablePacket};
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Confirms to the client the answer it gave to the personal information agreement.
+#[derive(Debug, Clone)]
+pub struct PIAgreementAck {
+    pub buffer: SendablePacketBuffer,
+    account_id: i32,
+    status: u8,
+}
+
+impl PIAgreementAck {
+    pub fn new(account_id: i32, status: u8) -> PIAgreementAck {
+        let mut inst = PIAgreementAck {
+            buffer: SendablePacketBuffer::new(),
+            account_id,
+            status,
+        };
+        let _ = inst.write_all();
+        inst
+    }
+    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+        self.buffer.write(LoginServerOpcodes::PiAgreementAck as u8)?;
+        self.buffer.write_i32(self.account_id)?;
+        self.buffer.write(self.status)?;
+        Ok(())
+    }
+}
+
+impl SendablePacket for PIAgreementAck {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,34 @@
+use l2_core::packets::common::{LoginServerOpcodes, SendablePacket};
+use l2_core::packets::write::SendablePacketBuffer;
+
+/// Tells the client whether the personal information agreement must be shown (status 1) or not.
+#[derive(Debug, Clone)]
+pub struct PIAgreementCheck {
+    pub buffer: SendablePacketBuffer,
+    account_id: i32,
+    status: u8,
+}
+
+impl PIAgreementCheck {
+    pub fn new(account_id: i32, status: u8) -> PIAgreementCheck {
+        let mut inst = PIAgreementCheck {
+            buffer: SendablePacketBuffer::new(),
+            account_id,
+            status,
+        };
+        let _ = inst.write_all();
+        inst
+    }
+    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+        self.buffer.write(LoginServerOpcodes::PiAgreementCheck as u8)?;
+        self.buffer.write_i32(self.account_id)?;
+        self.buffer.write(self.status)?;
+        Ok(())
+    }
+}
+
+impl SendablePacket for PIAgreementCheck {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,30 @@
+use l2_core::packets::common::{LoginServerOpcodes, PlayFailReasons, SendablePacket};
+use l2_core::packets::write::SendablePacketBuffer;
+
+#[derive(Debug, Clone)]
+pub struct PlayFail {
+    pub buffer: SendablePacketBuffer,
+    pub reason: PlayFailReasons,
+}
+
+impl PlayFail {
+    pub fn new(reason: PlayFailReasons) -> PlayFail {
+        let mut inst = PlayFail {
+            buffer: SendablePacketBuffer::new(),
+            reason,
+        };
+        let _ = inst.write_all();
+        inst
+    }
+    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+        self.buffer.write(LoginServerOpcodes::PlayFail as u8)?;
+        self.buffer.write(self.reason as u8)?;
+        Ok(())
+    }
+}
+
+impl SendablePacket for PlayFail {
+    fn get_buffer_mut(&mut self) -> &mut SendablePacketBuffer {
+        &mut self.buffer
+    }
+}
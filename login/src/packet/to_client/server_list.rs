@@ -5,6 +5,7 @@ use l2_core::packets::write::SendablePacketBuffer;
 use l2_core::traits::handlers::PacketHandler;
 use crate::client_thread::ClientHandler;
 use crate::dto::player::GSCharsInfo;
+use chrono::Utc;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -22,21 +23,24 @@ impl ServerList {
         println!("Server list: {servers:?}");
         let player_option = lc.get_player(username);
         let mut chars_on_server = None;
+        let mut last_server = 0;
         if let Some(player) = player_option {
+            last_server = i32::from(player.last_server);
             chars_on_server = Some(player.chars_on_servers);
         }
         let mut sl = Self {
             buffer: SendablePacketBuffer::new(),
             servers,
-            last_server: 0, //todo: implement me
+            last_server,
             chars_on_server,
         };
-        let _ = sl.write_all();
+        let _ = sl.write_all(Utc::now().timestamp());
         sl
     }
 
+    /// `now` is a unix timestamp in seconds, used to show how long is left until a character is deleted.
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-    fn write_all(&mut self) -> Result<(), anyhow::Error> {
+    fn write_all(&mut self, now: i64) -> Result<(), anyhow::Error> {
         {
             self.buffer.write(LoginServerOpcodes::ServerList as u8)?;
             self.buffer.write(self.servers.len() as u8)?;
@@ -75,8 +79,17 @@ impl ServerList {
         if let Some(ref servers) = self.chars_on_server {
             for (server_id, info) in servers {
                 self.buffer.write(*server_id)?;
-                // todo: here should be real count of chars on server
                 self.buffer.write(info.total_chars)?;
+                self.buffer.write(info.chars_to_delete)?;
+                for delete_at in info
+                    .char_deletion_timestamps
+                    .iter()
+                    .take(usize::from(info.chars_to_delete))
+                {
+                    let delete_in = (delete_at - now).max(0);
+                    self.buffer
+                        .write_i32(i32::try_from(delete_in).unwrap_or(i32::MAX))?;
+                }
             }
         }
         Ok(())
@@ -88,3 +101,40 @@ impl SendablePacket for ServerList {
         &mut self.buffer
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_chars_on_server() {
+        let chars_info = GSCharsInfo {
+            total_chars: 3,
+            chars_to_delete: 1,
+            char_deletion_timestamps: vec![1_000_600],
+        };
+        let mut sl = ServerList {
+            buffer: SendablePacketBuffer::new(),
+            servers: vec![],
+            last_server: 1,
+            chars_on_server: Some(HashMap::from([(1, chars_info)])),
+        };
+        sl.write_all(1_000_000).unwrap();
+        assert_eq!(
+            sl.buffer.get_data_mut(),
+            [14, 0, 4, 0, 1, 0xA4, 0, 1, 3, 1, 0x58, 0x02, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_no_chars_on_server() {
+        let mut sl = ServerList {
+            buffer: SendablePacketBuffer::new(),
+            servers: vec![],
+            last_server: 0,
+            chars_on_server: Some(HashMap::from([(2, GSCharsInfo::default())])),
+        };
+        sl.write_all(1_000_000).unwrap();
+        assert_eq!(sl.buffer.get_data_mut(), [10, 0, 4, 0, 0, 0xA4, 0, 2, 0, 0]);
+    }
+}
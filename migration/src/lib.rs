@@ -2,6 +2,13 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_user;
 mod m20241213_210106_create_char;
+mod m20241220_120000_add_user_pi_agreement;
+mod m20241222_100000_create_ip_ban;
+mod m20241224_100000_add_user_last_server;
+mod m20241226_100000_create_account_access_log;
+mod m20241228_100000_add_user_pin;
+mod m20241230_100000_create_item;
+mod m20250102_100000_unique_char_name;
 
 pub struct Migrator;
 
@@ -11,6 +18,13 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20220101_000001_create_user::Migration),
             Box::new(m20241213_210106_create_char::Migration),
+            Box::new(m20241220_120000_add_user_pi_agreement::Migration),
+            Box::new(m20241222_100000_create_ip_ban::Migration),
+            Box::new(m20241224_100000_add_user_last_server::Migration),
+            Box::new(m20241226_100000_create_account_access_log::Migration),
+            Box::new(m20241228_100000_add_user_pin::Migration),
+            Box::new(m20241230_100000_create_item::Migration),
+            Box::new(m20250102_100000_unique_char_name::Migration),
         ]
     }
 }
@@ -1,8 +1,8 @@
 use crate::m20220101_000001_create_user as previous;
-use sea_orm_migration::schema::{big_integer, big_unsigned, big_unsigned_null, double, double_null, integer_null, small_integer, small_unsigned, small_unsigned_null, string_len_null, timestamp_with_time_zone_null, tiny_unsigned, tiny_unsigned_null, unsigned, unsigned_null};
+use sea_orm_migration::schema::{big_unsigned, big_unsigned_null, double, integer_null, small_unsigned, small_unsigned_null, string_len_null, timestamp_with_time_zone_null, tiny_unsigned, tiny_unsigned_null, unsigned, unsigned_null};
 use sea_orm_migration::{
     prelude::*,
-    schema::{date_time_null, integer, pk_auto, string},
+    schema::{integer, pk_auto, string},
 };
 
 #[derive(DeriveMigrationName)]
@@ -102,7 +102,7 @@ impl MigrationTrait for Migration {
 }
 
 #[derive(DeriveIden)]
-enum Character {
+pub enum Character {
     Table,
     Id,
     Name,
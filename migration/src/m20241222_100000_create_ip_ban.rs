@@ -0,0 +1,40 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer_null, pk_auto, string_null, string_uniq},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IpBan::Table)
+                    .if_not_exists()
+                    .col(pk_auto(IpBan::Id))
+                    .col(string_uniq(IpBan::Network))
+                    .col(big_integer_null(IpBan::ExpiresAt))
+                    .col(string_null(IpBan::Reason))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IpBan::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum IpBan {
+    Table,
+    Id,
+    Network,
+    ExpiresAt,
+    Reason,
+}
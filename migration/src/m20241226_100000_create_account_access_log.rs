@@ -0,0 +1,72 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, big_integer_null, pk_auto, small_integer_null, string, string_null},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountAccessLog::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AccountAccessLog::Id))
+                    .col(string(AccountAccessLog::Account))
+                    .col(string_null(AccountAccessLog::Ip))
+                    .col(string_null(AccountAccessLog::PcIp))
+                    .col(string_null(AccountAccessLog::Hop1))
+                    .col(string_null(AccountAccessLog::Hop2))
+                    .col(string_null(AccountAccessLog::Hop3))
+                    .col(string_null(AccountAccessLog::Hop4))
+                    .col(small_integer_null(AccountAccessLog::GameServer))
+                    .col(big_integer(AccountAccessLog::LoginAt))
+                    .col(big_integer_null(AccountAccessLog::LogoutAt))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_access_log_account")
+                    .table(AccountAccessLog::Table)
+                    .col(AccountAccessLog::Account)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_access_log_ip")
+                    .table(AccountAccessLog::Table)
+                    .col(AccountAccessLog::Ip)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountAccessLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum AccountAccessLog {
+    Table,
+    Id,
+    Account,
+    Ip,
+    PcIp,
+    Hop1,
+    Hop2,
+    Hop3,
+    Hop4,
+    GameServer,
+    LoginAt,
+    LogoutAt,
+}
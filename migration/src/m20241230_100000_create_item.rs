@@ -0,0 +1,64 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, integer, pk_auto, string_len},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Item::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Item::Id))
+                    .col(integer(Item::Owner))
+                    .col(integer(Item::ItemId))
+                    .col(big_integer(Item::Count).default(1))
+                    .col(integer(Item::EnchantLevel).default(0))
+                    .col(string_len(Item::Loc, 16))
+                    .col(integer(Item::LocData).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_item_owner")
+                            .from(Item::Table, Item::Owner)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_item_owner")
+                    .table(Item::Table)
+                    .col(Item::Owner)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Item::Table).to_owned())
+            .await
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(DeriveIden)]
+pub enum Item {
+    Table,
+    Id,
+    Owner,
+    ItemId,
+    Count,
+    EnchantLevel,
+    Loc,
+    LocData,
+}
@@ -0,0 +1,50 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DbBackend;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Character names must be unique ignoring case, the name index is recreated as a unique one
+/// on `LOWER(name)`, so "Bob" and "bob" can't both exist on case-sensitive collations.
+/// Fails if such names already exist, one of them has to be renamed first.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_name")
+                    .table(Character::Table)
+                    .to_owned(),
+            )
+            .await?;
+        // sea-query can't build indexes on expressions
+        let sql = match manager.get_database_backend() {
+            DbBackend::MySql => "CREATE UNIQUE INDEX `idx_name` ON `character` ((LOWER(`name`)))",
+            _ => r#"CREATE UNIQUE INDEX "idx_name" ON "character" (LOWER("name"))"#,
+        };
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_name")
+                    .table(Character::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_name")
+                    .table(Character::Table)
+                    .col(Character::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+}